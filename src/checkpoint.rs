@@ -0,0 +1,108 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+use chrono::Utc;
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+use crate::component::repo::issue::IssueUpdate;
+use crate::result::Result;
+use crate::util::{get_command_line, get_data_dir};
+
+/// The step to run once all the issues of a checkpoint are updated.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PostStep {
+    CloseMilestone { number: u64, title: String },
+    DeleteLabel { name: String },
+}
+
+/// The resolved issues of a bulk issue update and the progress, to be able to resume it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub id: String,
+    pub command: String,
+    pub action: String,
+    pub owner: String,
+    pub repo: String,
+    pub issues: Vec<IssueUpdate>,
+    pub completed: Vec<u64>,
+    /// The ID of the journal entry reverted by the updates, if it's an undo.
+    #[serde(default)]
+    pub undo_of: Option<String>,
+    #[serde(default)]
+    pub post_step: Option<PostStep>,
+}
+
+impl Checkpoint {
+    pub fn new(owner: &str, repo: &str, action: &str, issues: Vec<IssueUpdate>) -> Self {
+        Checkpoint {
+            id: Utc::now().format("%Y%m%d%H%M%S%3f").to_string(),
+            command: get_command_line(),
+            action: action.to_string(),
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            issues,
+            completed: vec![],
+            undo_of: None,
+            post_step: None,
+        }
+    }
+
+    pub fn remaining_issues(&self) -> Vec<IssueUpdate> {
+        self.issues
+            .iter()
+            .filter(|it| !self.completed.contains(&it.number))
+            .cloned()
+            .collect()
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.issues
+            .iter()
+            .all(|it| self.completed.contains(&it.number))
+    }
+
+    pub fn path(&self) -> Result<PathBuf> {
+        Ok(checkpoint_dir()?.join(format!("{}.json", self.id)))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::create_dir_all(checkpoint_dir()?)?;
+
+        let path = self.path()?;
+        debug!("saving checkpoint: {:?}", path);
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+
+        Ok(())
+    }
+
+    pub fn remove(&self) -> Result<()> {
+        let path = self.path()?;
+        if path.exists() {
+            debug!("removing checkpoint: {:?}", path);
+            fs::remove_file(path)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn checkpoint_dir() -> Result<PathBuf> {
+    get_data_dir("checkpoint")
+}
+
+/// Loads the checkpoint by the file path or ID.
+pub fn load_checkpoint(checkpoint: &str) -> Result<Checkpoint> {
+    let path = if Path::new(checkpoint).is_file() {
+        PathBuf::from(checkpoint)
+    } else {
+        checkpoint_dir()?.join(format!("{}.json", checkpoint))
+    };
+
+    let content = fs::read_to_string(&path)
+        .map_err(|err| anyhow!("checkpoint {} not found: {}", checkpoint, err))?;
+
+    Ok(serde_json::from_str(&content)?)
+}
@@ -9,3 +9,21 @@ pub fn create_query_arg<'help>() -> Arg<'help> {
         .value_delimiter(' ')
         .takes_value(true)
 }
+
+pub fn create_comment_arg<'help>() -> Arg<'help> {
+    Arg::new("comment")
+        .value_name("template")
+        .help("Comment on issues")
+        .long_help("Comment on issues, which is a Tera template with the issue fields: number, title, url, state, author, labels, assignees, milestone")
+        .long("comment")
+        .takes_value(true)
+}
+
+pub fn create_users_arg<'help>() -> Arg<'help> {
+    Arg::new("users")
+        .value_name("users")
+        .help("GitHub users separated by comma")
+        .long("users")
+        .value_delimiter(',')
+        .takes_value(true)
+}
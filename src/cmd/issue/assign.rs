@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+use hubcaps_ex::issues::Issue;
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+
+use crate::cmd::arg::{create_query_arg, create_users_arg};
+use crate::cmd::issue::AssigneeRotation;
+use crate::cmd::CommandTrait;
+use crate::component::repo::issue::{IssueComponentTrait, IssueUpdate};
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::result::{CmdResult, Result};
+
+pub const CMD_ASSIGN_ISSUE: &str = "assign";
+
+pub struct AssignIssueCommand;
+
+impl AssignIssueCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for AssignIssueCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_ASSIGN_ISSUE)
+            .about("Assign users to issues")
+            .args([
+                create_query_arg(),
+                create_users_arg().required(true),
+                Arg::new("round-robin")
+                    .help("Assign unassigned issues to one of the users in turn, balanced by their open assigned issues")
+                    .long("round-robin"),
+            ])
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        let config = NoteConfig::new(matches);
+        let repo_component = RepoComponent::new(None, Arc::new(config));
+        let users: Vec<_> = matches.values_of("users").unwrap().collect();
+
+        let issues = progress!(
+            "Searching issues",
+            repo_component.search_all_issues_by_query(matches.value_of("query").unwrap_or_default()).await?;
+        );
+
+        if matches.is_present("round-robin") {
+            let issues_to_update = progress!(
+                "Counting assigned issues",
+                create_issues_info_to_assign_round_robin(&repo_component, &issues, &users).await?;
+            );
+
+            return self
+                .process_issue_updates(
+                    matches,
+                    &repo_component,
+                    issues_to_update,
+                    &format!("to assign in turn to {:?}", users),
+                )
+                .await;
+        }
+
+        let issues_to_update = issues
+            .iter()
+            .map(|it| {
+                let mut issue = IssueUpdate::new(it);
+                issue.add_assignees(&users);
+                issue
+            })
+            .collect();
+
+        self.process_issue_updates(
+            matches,
+            &repo_component,
+            issues_to_update,
+            &format!("to assign {:?}", users),
+        )
+        .await
+    }
+}
+
+async fn create_issues_info_to_assign_round_robin(
+    repo_component: &(dyn IssueComponentTrait + Send + Sync),
+    issues: &[Issue],
+    users: &[&str],
+) -> Result<Vec<IssueUpdate>> {
+    let mut rotation = AssigneeRotation::new(repo_component, users).await?;
+
+    let issues_to_update = issues
+        .iter()
+        .map(|it| {
+            let mut issue = IssueUpdate::new(it);
+
+            if issue.before.assignees.is_empty() {
+                if let Some(user) = rotation.next() {
+                    issue.add_assignees(&[user]);
+                }
+            }
+
+            issue
+        })
+        .collect();
+
+    Ok(issues_to_update)
+}
@@ -2,10 +2,10 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use clap::{Arg, ArgMatches, Command};
-use hubcaps_ex::issues::IssueOptions;
 use libcli_rs::progress::{ProgressBar, ProgressTrait};
 
 use crate::cmd::arg::create_query_arg;
+use crate::cmd::issue::create_issues_info_to_update_milestone;
 use crate::cmd::CommandTrait;
 use crate::component::repo::issue::IssueComponentTrait;
 use crate::component::repo::RepoComponent;
@@ -50,33 +50,14 @@ impl CommandTrait for AssignMilestoneCommand {
         let milestone = repo_component
             .get_milestone(matches.value_of("milestone").unwrap())
             .await?;
-        let issues_to_update: Vec<_> = issues
-            .into_iter()
-            .map(|it| {
-                (
-                    it.number,
-                    IssueOptions {
-                        title: it.title,
-                        body: it.body,
-                        assignee: None,
-                        assignees: Some(it.assignees.into_iter().map(|it| it.login).collect()),
-                        milestone: Some(milestone.number),
-                        labels: it.labels.into_iter().map(|it| it.name).collect(),
-                        state: it.state,
-                    },
-                )
-            })
-            .collect();
-
-        progress!(
-            format!("Updating issues to the milestone {}", milestone.title),
-            repo_component.update_issues(&issues_to_update).await?;
-        );
-
-        println!(
-            "Successfully updated issues to the milestone {}",
-            milestone.title
-        );
-        Ok(())
+        let issues_to_update = create_issues_info_to_update_milestone(&issues, Some(&milestone));
+
+        self.process_issue_updates(
+            matches,
+            &repo_component,
+            issues_to_update,
+            &format!("to the milestone {}", milestone.title),
+        )
+        .await
     }
 }
@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::io::stdout;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+use hubcaps_ex::issues::Issue;
+use hubcaps_ex::milestone::Milestone;
+use libcli_rs::output::{OutputFactory, OutputFormat, OutputTrait};
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+
+use crate::cmd::arg::create_query_arg;
+use crate::cmd::{confirm, CommandTrait};
+use crate::component::repo::issue::{IssueComponentTrait, IssueCreateOptions};
+use crate::component::repo::label::LabelComponentTrait;
+use crate::component::repo::milestone::MilestoneComponentTrait;
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::result::{CmdResult, Result};
+use crate::util::get_output_format_from_args;
+
+pub const CMD_BACKPORT_ISSUE: &str = "backport";
+
+const DEFAULT_BACKPORT_LABEL_PREFIX: &str = "backport/";
+
+pub struct BackportIssueCommand;
+
+#[derive(Clone, Serialize, Deserialize)]
+struct BackportPlan {
+    issue: u64,
+    version: String,
+    title: String,
+    milestone: String,
+    labels: Vec<String>,
+    /// The number of the backport issue already created.
+    existing_issue: Option<u64>,
+    #[serde(skip)]
+    milestone_number: u64,
+    #[serde(skip)]
+    body: String,
+    #[serde(skip)]
+    assignees: Vec<String>,
+}
+
+impl BackportIssueCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for BackportIssueCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_BACKPORT_ISSUE)
+            .about("Create backport issues of the issues with backport labels")
+            .args([
+                create_query_arg(),
+                Arg::new("label-prefix")
+                    .value_name("prefix")
+                    .help("Prefix of the backport labels followed by the version, e.g. backport/1.1.x")
+                    .long("label-prefix")
+                    .takes_value(true)
+                    .default_value(DEFAULT_BACKPORT_LABEL_PREFIX),
+            ])
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        let config = NoteConfig::new(matches);
+        let repo_component = RepoComponent::new(None, Arc::new(config));
+        let label_prefix = matches.value_of("label-prefix").unwrap();
+        let is_console = matches!(get_output_format_from_args(matches)?, OutputFormat::Console);
+
+        let labels = progress!("Listing labels", repo_component.list_labels().await?;);
+        let backport_labels: Vec<_> = labels
+            .into_iter()
+            .map(|it| it.name)
+            .filter(|it| it.starts_with(label_prefix))
+            .collect();
+        if backport_labels.is_empty() {
+            if !is_console {
+                return print_backport_plans(matches, &[]);
+            }
+            println!("No backport labels with the prefix {} found", label_prefix);
+            return Ok(());
+        }
+
+        let issues = progress!(
+            "Searching issues to backport",
+            repo_component.search_all_issues_by_query(&format!(
+                "{} is:issue label:{}",
+                matches.value_of("query").unwrap_or_default(),
+                backport_labels.join(",")
+            )).await?;
+        );
+        let backport_issues = progress!(
+            "Searching backport issues",
+            repo_component.search_all_issues_by_query("is:issue in:title \"[BACKPORT]\"").await?;
+        );
+        let milestones = progress!(
+            "Listing open milestones",
+            repo_component.list_milestones("open").await?;
+        );
+
+        let mut plans = vec![];
+        for issue in &issues {
+            for label in issue
+                .labels
+                .iter()
+                .filter(|it| it.name.starts_with(label_prefix))
+            {
+                plans.push(create_backport_plan(
+                    issue,
+                    &label.name[label_prefix.len()..],
+                    label_prefix,
+                    &milestones,
+                    &backport_issues,
+                )?);
+            }
+        }
+        print_backport_plans(matches, &plans)?;
+
+        let plans: Vec<_> = plans
+            .into_iter()
+            .filter(|it| it.existing_issue.is_none())
+            .collect();
+        if plans.is_empty() || matches.is_present("dry-run") {
+            return Ok(());
+        }
+
+        confirm(
+            matches,
+            &format!("{} backport issues will be created", plans.len()),
+        )?;
+
+        for plan in &plans {
+            let created_issue = progress!(
+                format!("Creating the backport issue of #{} for {}", plan.issue, plan.version),
+                repo_component.create_issue(&IssueCreateOptions {
+                    title: plan.title.clone(),
+                    body: Some(plan.body.clone()),
+                    labels: plan.labels.clone(),
+                    assignees: plan.assignees.clone(),
+                    milestone: Some(plan.milestone_number),
+                }).await?;
+            );
+            if is_console {
+                println!("#{} {}", created_issue.number, created_issue.html_url);
+            }
+        }
+
+        if is_console {
+            println!("Successfully created {} backport issues", plans.len());
+        }
+        Ok(())
+    }
+}
+
+fn create_backport_plan(
+    issue: &Issue,
+    version: &str,
+    label_prefix: &str,
+    milestones: &[Milestone],
+    backport_issues: &[Issue],
+) -> Result<BackportPlan> {
+    let version = format!("v{}", version.trim_start_matches('v'));
+    let title = format!("[BACKPORT][{}] {}", version, issue.title);
+    let marker = format!("<!-- renote:backport:{}:{} -->", issue.number, version);
+
+    let existing_issue = backport_issues
+        .iter()
+        .find(|it| it.title == title || it.body.as_deref().unwrap_or_default().contains(&marker))
+        .map(|it| it.number);
+
+    // the milestone is only required to create the backport issue
+    let milestone = find_backport_milestone(milestones, &version);
+    if milestone.is_none() && existing_issue.is_none() {
+        return Err(anyhow!(
+            "no open milestone found for the backport {}",
+            version
+        ));
+    }
+
+    Ok(BackportPlan {
+        issue: issue.number,
+        title,
+        milestone: milestone.map(|it| it.title.clone()).unwrap_or_default(),
+        labels: issue
+            .labels
+            .iter()
+            .map(|it| it.name.clone())
+            .filter(|it| !it.starts_with(label_prefix))
+            .collect(),
+        existing_issue,
+        milestone_number: milestone.map(|it| it.number).unwrap_or_default(),
+        body: format!(
+            "Backport of #{} to {}.\n\n{}",
+            issue.number, version, marker
+        ),
+        assignees: issue.assignees.iter().map(|it| it.login.clone()).collect(),
+        version,
+    })
+}
+
+/// Finds the earliest open milestone of the version like `v1.1.x`, i.e. the next patch release.
+fn find_backport_milestone<'a>(
+    milestones: &'a [Milestone],
+    version: &str,
+) -> Option<&'a Milestone> {
+    let prefix = version.trim_end_matches('x');
+    let mut versions: HashMap<u64, Version> = HashMap::new();
+
+    for milestone in milestones.iter().filter(|it| it.title.starts_with(prefix)) {
+        if let Ok(version) = Version::parse(milestone.title.trim_start_matches('v')) {
+            versions.insert(milestone.number, version);
+        }
+    }
+
+    milestones
+        .iter()
+        .filter(|it| versions.contains_key(&it.number))
+        .min_by(|a, b| versions[&a.number].cmp(&versions[&b.number]))
+}
+
+fn print_backport_plans(matches: &ArgMatches, plans: &[BackportPlan]) -> CmdResult {
+    let output_format = get_output_format_from_args(matches)?;
+    if !matches!(output_format, OutputFormat::Console) {
+        return output!(output_format, .display(stdout(), &plans.to_vec(), None, None));
+    }
+
+    for plan in plans {
+        match plan.existing_issue {
+            Some(number) => println!("= {} (already created as #{})", plan.title, number),
+            None => {
+                println!("+ {}", plan.title);
+                println!("  milestone: {}", plan.milestone);
+                println!("  labels:    {:?}", plan.labels);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use std::fs;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+use hubcaps_ex::issues::Issue;
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+
+use crate::cmd::arg::create_query_arg;
+use crate::cmd::issue::{create_comment_marker, render_issue_comment};
+use crate::cmd::CommandTrait;
+use crate::component::repo::issue::{IssueComponentTrait, IssueUpdate};
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::result::{CmdResult, Result};
+
+pub const CMD_COMMENT_ISSUE: &str = "comment";
+
+pub struct CommentIssueCommand;
+
+impl CommentIssueCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for CommentIssueCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_COMMENT_ISSUE)
+            .about("Comment on issues")
+            .args([
+                create_query_arg(),
+                Arg::new("body")
+                    .value_name("template")
+                    .help("Comment body")
+                    .long_help("Comment body, which is a Tera template with the issue fields: number, title, url, state, author, labels, assignees, milestone")
+                    .short('b')
+                    .long("body")
+                    .takes_value(true)
+                    .conflicts_with("body-file")
+                    .required_unless_present("body-file"),
+                Arg::new("body-file")
+                    .value_name("file")
+                    .help("Comment body template file")
+                    .long("body-file")
+                    .takes_value(true),
+                Arg::new("marker")
+                    .value_name("string")
+                    .help("Hidden marker to skip the issues already commented [default: hash of the body]")
+                    .long("marker")
+                    .takes_value(true),
+            ])
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        let config = NoteConfig::new(matches);
+        let repo_component = RepoComponent::new(None, Arc::new(config));
+
+        let body = match matches.value_of("body-file") {
+            Some(file) => fs::read_to_string(file)
+                .map_err(|err| anyhow!("failed to read comment body file {}: {}", file, err))?,
+            None => matches.value_of("body").unwrap_or_default().to_string(),
+        };
+        let marker = create_comment_marker(&create_marker_id(matches.value_of("marker"), &body));
+
+        let issues = progress!(
+            "Searching issues",
+            repo_component.search_all_issues_by_query(matches.value_of("query").unwrap_or_default()).await?;
+        );
+
+        let issues_to_update = progress!(
+            "Checking commented issues",
+            create_issues_info_to_comment(&repo_component, &issues, &body, &marker).await?;
+        );
+
+        self.process_issue_updates(matches, &repo_component, issues_to_update, "to comment on")
+            .await
+    }
+}
+
+async fn create_issues_info_to_comment(
+    repo_component: &(dyn IssueComponentTrait + Send + Sync),
+    issues: &[Issue],
+    template: &str,
+    marker: &str,
+) -> Result<Vec<IssueUpdate>> {
+    let mut issues_to_update = vec![];
+
+    for issue in issues {
+        let mut issue_to_update = IssueUpdate::new(issue);
+        issue_to_update.comment =
+            render_issue_comment(repo_component, issue, template, marker).await?;
+
+        issues_to_update.push(issue_to_update);
+    }
+
+    Ok(issues_to_update)
+}
+
+/// Uses the hash of the template as the marker by default, so re-runs of the same comment are skipped.
+fn create_marker_id(marker: Option<&str>, template: &str) -> String {
+    match marker {
+        Some(marker) => marker.to_string(),
+        None => {
+            // FNV-1a, as the hash has to be stable across builds
+            let hash = template.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+            });
+            format!("{:016x}", hash)
+        }
+    }
+}
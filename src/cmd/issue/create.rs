@@ -0,0 +1,310 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::stdout;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+use hubcaps_ex::issues::Issue;
+use libcli_rs::output::{OutputFactory, OutputFormat, OutputTrait};
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+use serde::{Deserialize, Serialize};
+use tera::{Context, Tera};
+
+use crate::cmd::{confirm, CommandTrait};
+use crate::component::repo::issue::{IssueComponentTrait, IssueCreateOptions};
+use crate::component::repo::milestone::MilestoneComponentTrait;
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::result::{CmdResult, Result};
+use crate::util::get_output_format_from_args;
+
+pub const CMD_CREATE_ISSUE: &str = "create";
+
+/// The separator of the labels and assignees in a csv file.
+const CSV_LIST_SEPARATOR: char = ';';
+
+pub struct CreateIssueCommand;
+
+#[derive(Debug, Deserialize)]
+struct IssueDefinitions {
+    issues: Vec<IssueDefinition>,
+}
+
+/// The issue to create, of which all fields are Tera templates with the variables by `--var`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct IssueDefinition {
+    /// The idempotency key recorded in the issue body, the title by default.
+    key: Option<String>,
+    title: String,
+    body: Option<String>,
+    labels: Vec<String>,
+    assignees: Vec<String>,
+    milestone: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct IssueCreatePlan {
+    #[serde(flatten)]
+    issue: IssueDefinition,
+    /// The number of the issue already created by the key.
+    existing_issue: Option<u64>,
+}
+
+impl CreateIssueCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for CreateIssueCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_CREATE_ISSUE)
+            .about("Create issues from a yaml or csv file")
+            .args([
+                Arg::new("from")
+                    .value_name("file")
+                    .help("Issue definition yaml or csv file")
+                    .long("from")
+                    .required(true)
+                    .takes_value(true),
+                Arg::new("var")
+                    .value_name("name=value")
+                    .help("Template variable of the issue definitions, e.g. version=v1.2.0")
+                    .long("var")
+                    .multiple_occurrences(true)
+                    .takes_value(true),
+            ])
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        let config = NoteConfig::new(matches);
+        let repo_component = RepoComponent::new(None, Arc::new(config));
+
+        let mut context = Context::new();
+        for var in matches.values_of("var").into_iter().flatten() {
+            let (name, value) = var
+                .split_once('=')
+                .ok_or_else(|| anyhow!("invalid variable {}, expect name=value", var))?;
+            context.insert(name, value);
+        }
+
+        let issues = load_issue_definitions(matches.value_of("from").unwrap())?
+            .into_iter()
+            .map(|it| render_issue_definition(it, &context))
+            .collect::<Result<Vec<_>>>()?;
+
+        let created_issues = progress!(
+            "Listing issues",
+            repo_component.list_all_issues(&[]).await?;
+        );
+        let plans: Vec<_> = issues
+            .into_iter()
+            .map(|issue| IssueCreatePlan {
+                existing_issue: find_created_issue(&created_issues, &issue),
+                issue,
+            })
+            .collect();
+        print_issue_create_plans(matches, &plans)?;
+
+        let issues: Vec<_> = plans
+            .into_iter()
+            .filter(|it| it.existing_issue.is_none())
+            .map(|it| it.issue)
+            .collect();
+        if issues.is_empty() || matches.is_present("dry-run") {
+            return Ok(());
+        }
+
+        confirm(matches, &format!("{} issues will be created", issues.len()))?;
+
+        let is_console = matches!(get_output_format_from_args(matches)?, OutputFormat::Console);
+        let mut milestones = HashMap::new();
+        for issue in &issues {
+            let milestone = match &issue.milestone {
+                Some(title) => {
+                    if !milestones.contains_key(title) {
+                        let milestone = repo_component.find_milestone(title).await?;
+                        milestones.insert(title.clone(), milestone.number);
+                    }
+                    milestones.get(title).copied()
+                }
+                None => None,
+            };
+
+            let created_issue = progress!(
+                format!("Creating the issue {}", issue.title),
+                repo_component.create_issue(&IssueCreateOptions {
+                    title: issue.title.clone(),
+                    body: Some(format!(
+                        "{}\n\n{}",
+                        issue.body.as_deref().unwrap_or_default().trim_end(),
+                        create_issue_marker(issue)
+                    )),
+                    labels: issue.labels.clone(),
+                    assignees: issue.assignees.clone(),
+                    milestone,
+                }).await?;
+            );
+            if is_console {
+                println!("#{} {}", created_issue.number, created_issue.html_url);
+            }
+        }
+
+        if is_console {
+            println!("Successfully created {} issues", issues.len());
+        }
+        Ok(())
+    }
+}
+
+fn load_issue_definitions(path: &str) -> Result<Vec<IssueDefinition>> {
+    let content =
+        fs::read_to_string(path).map_err(|err| anyhow!("failed to read {}: {}", path, err))?;
+
+    if path.ends_with(".csv") {
+        return parse_csv_issue_definitions(&content)
+            .map_err(|err| anyhow!("failed to parse {}: {}", path, err));
+    }
+
+    let definitions: IssueDefinitions = serde_yaml::from_str(&content)
+        .map_err(|err| anyhow!("failed to parse {}: {}", path, err))?;
+    Ok(definitions.issues)
+}
+
+/// Parses the csv with the header of the issue definition fields, e.g. `key,title,body,labels`.
+/// The labels and assignees are separated by `;`.
+fn parse_csv_issue_definitions(content: &str) -> Result<Vec<IssueDefinition>> {
+    let mut rows = parse_csv_rows(content).into_iter();
+    let header = rows.next().ok_or_else(|| anyhow!("header not found"))?;
+
+    let split_list = |value: String| -> Vec<String> {
+        value
+            .split(CSV_LIST_SEPARATOR)
+            .map(|it| it.trim().to_string())
+            .filter(|it| !it.is_empty())
+            .collect()
+    };
+    let non_empty = |value: String| Some(value).filter(|it| !it.is_empty());
+
+    rows.filter(|row| row.iter().any(|it| !it.trim().is_empty()))
+        .map(|row| {
+            let mut definition = IssueDefinition::default();
+            for (column, value) in header.iter().zip(row) {
+                match column.trim() {
+                    "key" => definition.key = non_empty(value),
+                    "title" => definition.title = value,
+                    "body" => definition.body = non_empty(value),
+                    "labels" => definition.labels = split_list(value),
+                    "assignees" => definition.assignees = split_list(value),
+                    "milestone" => definition.milestone = non_empty(value),
+                    column => return Err(anyhow!("unknown column {}", column)),
+                }
+            }
+            Ok(definition)
+        })
+        .collect()
+}
+
+/// Splits the csv into rows of fields, supporting quoted fields with commas, line breaks and
+/// escaped quotes.
+fn parse_csv_rows(content: &str) -> Vec<Vec<String>> {
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => row.push(std::mem::take(&mut field)),
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            '\r' if !quoted => {}
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    rows
+}
+
+fn render_issue_definition(issue: IssueDefinition, context: &Context) -> Result<IssueDefinition> {
+    let render = |template: &str| Tera::one_off(template, context, false);
+    let render_all = |templates: &[String]| {
+        templates
+            .iter()
+            .map(|it| render(it))
+            .collect::<tera::Result<Vec<_>>>()
+    };
+
+    let title = render(&issue.title)?;
+    if title.trim().is_empty() {
+        return Err(anyhow!("issue title is required"));
+    }
+
+    Ok(IssueDefinition {
+        key: issue.key.as_deref().map(render).transpose()?,
+        title,
+        body: issue.body.as_deref().map(render).transpose()?,
+        labels: render_all(&issue.labels)?,
+        assignees: render_all(&issue.assignees)?,
+        milestone: issue.milestone.as_deref().map(render).transpose()?,
+    })
+}
+
+/// Creates the hidden HTML comment in the issue body to identify the created issue on re-runs.
+fn create_issue_marker(issue: &IssueDefinition) -> String {
+    format!(
+        "<!-- renote:issue:{} -->",
+        issue.key.as_deref().unwrap_or(&issue.title)
+    )
+}
+
+/// Finds the issue created by the key or title among all issues, by the marker in the body.
+fn find_created_issue(issues: &[Issue], issue: &IssueDefinition) -> Option<u64> {
+    let marker = create_issue_marker(issue);
+
+    issues
+        .iter()
+        .find(|it| it.body.as_deref().unwrap_or_default().contains(&marker))
+        .map(|it| it.number)
+}
+
+fn print_issue_create_plans(matches: &ArgMatches, plans: &[IssueCreatePlan]) -> CmdResult {
+    let output_format = get_output_format_from_args(matches)?;
+    if !matches!(output_format, OutputFormat::Console) {
+        return output!(output_format, .display(stdout(), &plans.to_vec(), None, None));
+    }
+
+    for plan in plans {
+        let issue = &plan.issue;
+        match plan.existing_issue {
+            Some(number) => println!("= {} (already created as #{})", issue.title, number),
+            None => {
+                println!("+ {}", issue.title);
+                println!("  labels:    {:?}", issue.labels);
+                println!("  assignees: {:?}", issue.assignees);
+                println!(
+                    "  milestone: {}",
+                    issue.milestone.as_deref().unwrap_or("N/A")
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,283 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::stdout;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+use hubcaps_ex::issues::Issue;
+use libcli_rs::output::{OutputFactory, OutputFormat, OutputTrait};
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+use serde::{Deserialize, Serialize};
+
+use crate::cmd::arg::create_query_arg;
+use crate::cmd::issue::{create_comment_marker, render_issue_comment};
+use crate::cmd::CommandTrait;
+use crate::component::repo::issue::{IssueComponentTrait, IssueUpdate};
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::result::CmdResult;
+use crate::util::get_output_format_from_args;
+
+pub const CMD_DUPLICATE_ISSUES: &str = "duplicates";
+
+const DEFAULT_DUPLICATES_QUERY: &str = "is:issue is:open";
+
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "but", "by", "can", "do", "for", "from", "has",
+    "have", "if", "in", "is", "it", "not", "of", "on", "or", "so", "that", "the", "this", "to",
+    "was", "we", "when", "with",
+];
+
+/// The title counts more than the body, as it is usually the summary of the issue.
+const TITLE_WEIGHT: usize = 2;
+
+pub struct DuplicateIssuesCommand;
+
+#[derive(Clone, Serialize, Deserialize)]
+struct DuplicateCluster {
+    issues: Vec<IssueSummary>,
+    pairs: Vec<DuplicatePair>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct IssueSummary {
+    number: u64,
+    title: String,
+    url: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct DuplicatePair {
+    issue: u64,
+    other_issue: u64,
+    similarity: f64,
+}
+
+impl DuplicateIssuesCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for DuplicateIssuesCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_DUPLICATE_ISSUES)
+            .about("Find duplicate issue candidates by title and body similarity")
+            .args([
+                create_query_arg(),
+                Arg::new("threshold")
+                    .value_name("number")
+                    .help("Minimum similarity between 0 and 1 of the duplicate candidates")
+                    .long("threshold")
+                    .takes_value(true)
+                    .default_value("0.6"),
+                Arg::new("label")
+                    .value_name("label")
+                    .help("Label the newer issues of the candidates, e.g. duplicate?")
+                    .long("label")
+                    .takes_value(true),
+                Arg::new("comment")
+                    .help("Comment on the newer issues of the candidates linking the older ones")
+                    .long("comment"),
+            ])
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        let config = NoteConfig::new(matches);
+        let repo_component = RepoComponent::new(None, Arc::new(config));
+        let threshold: f64 = matches.value_of_t("threshold")?;
+
+        let issues = progress!(
+            "Searching issues",
+            repo_component.search_all_issues_by_query(matches.value_of("query").unwrap_or(DEFAULT_DUPLICATES_QUERY)).await?;
+        );
+
+        let clusters = create_duplicate_clusters(&issues, threshold);
+        print_duplicate_clusters(matches, &clusters)?;
+
+        let label = matches.value_of("label");
+        if label.is_none() && !matches.is_present("comment") {
+            return Ok(());
+        }
+
+        let marker = create_comment_marker("duplicates");
+        let mut issues_to_update = vec![];
+        for cluster in &clusters {
+            // the oldest issue is regarded as the original one
+            let (original, candidates) = cluster.issues.split_first().unwrap();
+
+            for candidate in candidates {
+                let issue = issues
+                    .iter()
+                    .find(|it| it.number == candidate.number)
+                    .unwrap();
+                let mut issue_to_update = IssueUpdate::new(issue);
+
+                if let Some(label) = label {
+                    issue_to_update.add_labels(&[label]);
+                }
+                if matches.is_present("comment") {
+                    let comment =
+                        format!("This issue might be a duplicate of #{}", original.number);
+                    issue_to_update.comment =
+                        render_issue_comment(&repo_component, issue, &comment, &marker).await?;
+                }
+
+                issues_to_update.push(issue_to_update);
+            }
+        }
+
+        self.process_issue_updates(
+            matches,
+            &repo_component,
+            issues_to_update,
+            "as duplicate candidates",
+        )
+        .await
+    }
+}
+
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(|it| it.to_lowercase())
+        .filter(|it| it.len() > 1 && !STOP_WORDS.contains(&it.as_str()))
+}
+
+/// Creates the TF-IDF vectors of the issue titles and bodies, normalized to compute the cosine
+/// similarity by the dot product.
+fn create_tf_idf_vectors(issues: &[Issue]) -> Vec<HashMap<String, f64>> {
+    let term_counts: Vec<HashMap<String, usize>> = issues
+        .iter()
+        .map(|issue| {
+            let mut counts = HashMap::new();
+            for token in tokenize(&issue.title) {
+                *counts.entry(token).or_insert(0) += TITLE_WEIGHT;
+            }
+            for token in tokenize(issue.body.as_deref().unwrap_or_default()) {
+                *counts.entry(token).or_insert(0) += 1;
+            }
+            counts
+        })
+        .collect();
+
+    let mut document_counts: HashMap<&str, usize> = HashMap::new();
+    for counts in &term_counts {
+        for token in counts.keys() {
+            *document_counts.entry(token.as_str()).or_insert(0) += 1;
+        }
+    }
+
+    let issue_count = issues.len() as f64;
+    term_counts
+        .iter()
+        .map(|counts| {
+            let mut vector: HashMap<String, f64> = counts
+                .iter()
+                .map(|(token, count)| {
+                    let document_count = document_counts[token.as_str()] as f64;
+                    let idf = ((1.0 + issue_count) / (1.0 + document_count)).ln() + 1.0;
+                    (token.clone(), *count as f64 * idf)
+                })
+                .collect();
+
+            let norm = vector.values().map(|it| it * it).sum::<f64>().sqrt();
+            if norm > 0.0 {
+                vector.values_mut().for_each(|it| *it /= norm);
+            }
+            vector
+        })
+        .collect()
+}
+
+fn cosine_similarity(vector: &HashMap<String, f64>, other_vector: &HashMap<String, f64>) -> f64 {
+    vector
+        .iter()
+        .filter_map(|(token, weight)| other_vector.get(token).map(|it| it * weight))
+        .sum()
+}
+
+/// Clusters the issues connected by the pairs above the threshold, ordered by the issue number.
+fn create_duplicate_clusters(issues: &[Issue], threshold: f64) -> Vec<DuplicateCluster> {
+    let vectors = create_tf_idf_vectors(issues);
+
+    // union-find by the index of the issues
+    let mut parents: Vec<usize> = (0..issues.len()).collect();
+    fn find_root(parents: &mut [usize], index: usize) -> usize {
+        let mut root = index;
+        while parents[root] != root {
+            root = parents[root];
+        }
+        parents[index] = root;
+        root
+    }
+
+    let mut pairs = vec![];
+    for i in 0..issues.len() {
+        for j in i + 1..issues.len() {
+            let similarity = cosine_similarity(&vectors[i], &vectors[j]);
+            if similarity < threshold {
+                continue;
+            }
+
+            let (root, other_root) = (find_root(&mut parents, i), find_root(&mut parents, j));
+            parents[other_root] = root;
+            pairs.push((i, j, similarity));
+        }
+    }
+
+    let mut clusters: BTreeMap<usize, (HashSet<usize>, Vec<DuplicatePair>)> = BTreeMap::new();
+    for (i, j, similarity) in pairs {
+        let (indexes, pairs) = clusters.entry(find_root(&mut parents, i)).or_default();
+        indexes.insert(i);
+        indexes.insert(j);
+        pairs.push(DuplicatePair {
+            issue: issues[i].number.min(issues[j].number),
+            other_issue: issues[i].number.max(issues[j].number),
+            similarity: (similarity * 100.0).round() / 100.0,
+        });
+    }
+
+    let mut clusters: Vec<_> = clusters
+        .into_values()
+        .map(|(indexes, pairs)| {
+            let mut issues: Vec<_> = indexes
+                .into_iter()
+                .map(|it| IssueSummary {
+                    number: issues[it].number,
+                    title: issues[it].title.clone(),
+                    url: issues[it].html_url.clone(),
+                })
+                .collect();
+            issues.sort_by_key(|it| it.number);
+
+            DuplicateCluster { issues, pairs }
+        })
+        .collect();
+    clusters.sort_by_key(|it| it.issues[0].number);
+
+    clusters
+}
+
+fn print_duplicate_clusters(matches: &ArgMatches, clusters: &[DuplicateCluster]) -> CmdResult {
+    let output_format = get_output_format_from_args(matches)?;
+    if !matches!(output_format, OutputFormat::Console) {
+        return output!(output_format, .display(stdout(), &clusters.to_vec(), None, None));
+    }
+
+    println!("Duplicate candidates: {} clusters", clusters.len());
+    for cluster in clusters {
+        println!();
+        for issue in &cluster.issues {
+            println!("  - #{} {} ({})", issue.number, issue.title, issue.url);
+        }
+        for pair in &cluster.pairs {
+            println!(
+                "    #{} ~ #{}: {:.2}",
+                pair.issue, pair.other_issue, pair.similarity
+            );
+        }
+    }
+
+    Ok(())
+}
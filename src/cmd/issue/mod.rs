@@ -1,21 +1,45 @@
 use async_trait::async_trait;
-use hubcaps_ex::issues::IssueOptions;
+use hubcaps_ex::issues::Issue;
+use hubcaps_ex::milestone::Milestone;
 use libcli_rs::progress::{ProgressBar, ProgressTrait};
+use log::debug;
+use serde::Serialize;
+use tera::{Context, Tera};
 
 pub use add_label::{AddLabelCommand, CMD_ADD_LABEL};
+pub use assign::{AssignIssueCommand, CMD_ASSIGN_ISSUE};
 pub use assign_milestone::{AssignMilestoneCommand, CMD_ASSIGN_MILESTONE};
+pub use backport::{BackportIssueCommand, CMD_BACKPORT_ISSUE};
+pub use close::{CloseIssueCommand, CMD_CLOSE_ISSUE};
+pub use comment::{CommentIssueCommand, CMD_COMMENT_ISSUE};
+pub use create::{CreateIssueCommand, CMD_CREATE_ISSUE};
+pub use duplicates::{DuplicateIssuesCommand, CMD_DUPLICATE_ISSUES};
 pub use remove_label::{RemoveLabelCommand, CMD_REMOVE_LABEL};
+pub use reopen::{ReopenIssueCommand, CMD_REOPEN_ISSUE};
+pub use stale::{StaleIssueCommand, CMD_STALE_ISSUE};
+pub use transfer::{TransferIssueCommand, CMD_TRANSFER_ISSUE};
+pub use unassign::{UnassignIssueCommand, CMD_UNASSIGN_ISSUE};
 pub use unassign_milestone::{UnassignMilestoneCommand, CMD_UNASSIGN_MILESTONE};
 
 use crate::cmd::issue::search::{SearchIssueCommand, CMD_ISSUE_SEARCH};
 use crate::cmd::{create_cmd, CommandSetting, CommandTrait};
-use crate::component::repo::issue::IssueComponentTrait;
+use crate::component::repo::issue::{IssueComponentTrait, IssueMilestone, IssueUpdate};
 use crate::result::Result;
 
 mod add_label;
+mod assign;
 mod assign_milestone;
+mod backport;
+mod close;
+mod comment;
+mod create;
+mod duplicates;
 mod remove_label;
+mod reopen;
 mod search;
+mod stale;
+mod transfer;
+mod unassign;
 mod unassign_milestone;
 
 pub const CMD_ISSUE: &str = "issue";
@@ -29,6 +53,24 @@ enum IssueLabelUpdateType {
     Remove,
 }
 
+/// The triage rotation assigning issues to the user with the fewest open assigned issues.
+pub(crate) struct AssigneeRotation {
+    users: Vec<(String, usize)>,
+}
+
+/// The issue fields available in the issue templates like comments.
+#[derive(Serialize)]
+struct IssueTemplateContext<'a> {
+    number: u64,
+    title: &'a str,
+    url: &'a str,
+    state: &'a str,
+    author: &'a str,
+    labels: Vec<&'a str>,
+    assignees: Vec<&'a str>,
+    milestone: Option<&'a str>,
+}
+
 impl IssueCommand {
     pub fn new() -> Self {
         IssueCommand {
@@ -40,6 +82,16 @@ impl IssueCommand {
                     CMD_REMOVE_LABEL => create_cmd(Box::new(RemoveLabelCommand::new())),
                     CMD_ASSIGN_MILESTONE => create_cmd(Box::new(AssignMilestoneCommand::new())),
                     CMD_UNASSIGN_MILESTONE => create_cmd(Box::new(UnassignMilestoneCommand::new())),
+                    CMD_CLOSE_ISSUE => create_cmd(Box::new(CloseIssueCommand::new())),
+                    CMD_REOPEN_ISSUE => create_cmd(Box::new(ReopenIssueCommand::new())),
+                    CMD_COMMENT_ISSUE => create_cmd(Box::new(CommentIssueCommand::new())),
+                    CMD_ASSIGN_ISSUE => create_cmd(Box::new(AssignIssueCommand::new())),
+                    CMD_UNASSIGN_ISSUE => create_cmd(Box::new(UnassignIssueCommand::new())),
+                    CMD_STALE_ISSUE => create_cmd(Box::new(StaleIssueCommand::new())),
+                    CMD_DUPLICATE_ISSUES => create_cmd(Box::new(DuplicateIssuesCommand::new())),
+                    CMD_CREATE_ISSUE => create_cmd(Box::new(CreateIssueCommand::new())),
+                    CMD_BACKPORT_ISSUE => create_cmd(Box::new(BackportIssueCommand::new())),
+                    CMD_TRANSFER_ISSUE => create_cmd(Box::new(TransferIssueCommand::new())),
                     CMD_ISSUE_SEARCH => create_cmd(Box::new(SearchIssueCommand::new())),
                 },
             },
@@ -47,6 +99,34 @@ impl IssueCommand {
     }
 }
 
+impl AssigneeRotation {
+    pub(crate) async fn new(
+        repo_component: &(dyn IssueComponentTrait + Send + Sync),
+        users: &[&str],
+    ) -> Result<Self> {
+        let mut rotation = vec![];
+        for user in users {
+            let count = repo_component
+                .count_issues_by_query(&format!("is:open assignee:{}", user))
+                .await?;
+            rotation.push((user.to_string(), count));
+        }
+
+        Ok(AssigneeRotation { users: rotation })
+    }
+
+    /// Returns the first user with the fewest assigned issues, so ties keep the rotation order.
+    pub(crate) fn next(&mut self) -> Option<&str> {
+        let (user, count) = self
+            .users
+            .iter_mut()
+            .reduce(|min, it| if it.1 < min.1 { it } else { min })?;
+        *count += 1;
+
+        Some(user)
+    }
+}
+
 #[async_trait]
 impl CommandTrait for IssueCommand {
     fn setting(&self) -> &CommandSetting {
@@ -59,38 +139,99 @@ async fn create_issues_info_to_update(
     query: &str,
     filtered_labels: &[&str],
     update_type: &IssueLabelUpdateType,
-) -> Result<Vec<(u64, IssueOptions)>> {
+) -> Result<Vec<IssueUpdate>> {
     let issues = progress!(
         "Searching issues",
         repo_component.search_issues_by_query(query).await?;
     );
 
     let issues_info = issues
-        .into_iter()
+        .iter()
         .map(|it| {
-            let iter = it.labels.iter().map(|it| it.name.clone());
-            let labels = if matches!(update_type, IssueLabelUpdateType::Add) {
-                iter.chain(filtered_labels.iter().map(|l| l.to_string()))
-                    .collect()
+            let mut issue = IssueUpdate::new(it);
+            if matches!(update_type, IssueLabelUpdateType::Add) {
+                issue.add_labels(filtered_labels);
             } else {
-                iter.filter(|l| !filtered_labels.contains(&l.as_str()))
-                    .collect()
-            };
-
-            (
-                it.number,
-                IssueOptions {
-                    title: it.title,
-                    body: it.body,
-                    assignee: None,
-                    assignees: Some(it.assignees.into_iter().map(|it| it.login).collect()),
-                    milestone: it.milestone.map(|it| it.number),
-                    labels,
-                    state: it.state,
-                },
-            )
+                issue.remove_labels(filtered_labels);
+            }
+
+            issue
         })
         .collect();
 
     Ok(issues_info)
 }
+
+pub(crate) fn create_issues_info_to_update_milestone(
+    issues: &[Issue],
+    milestone: Option<&Milestone>,
+) -> Vec<IssueUpdate> {
+    issues
+        .iter()
+        .map(|it| {
+            let mut issue = IssueUpdate::new(it);
+            issue.after.milestone = milestone.map(IssueMilestone::from);
+
+            issue
+        })
+        .collect()
+}
+
+fn create_issues_info_to_update_state(
+    issues: &[Issue],
+    state: &str,
+    state_reason: Option<&str>,
+    comment: Option<&str>,
+) -> Result<Vec<IssueUpdate>> {
+    issues
+        .iter()
+        .map(|it| {
+            let mut issue = IssueUpdate::new(it);
+            issue.after.state = state.to_string();
+            issue.state_reason = state_reason.map(String::from);
+            issue.comment = comment
+                .map(|template| render_issue_template(template, it))
+                .transpose()?;
+
+            Ok(issue)
+        })
+        .collect()
+}
+
+/// Renders the Tera template with the issue fields, e.g. `{{ title }} (#{{ number }})`.
+pub(crate) fn render_issue_template(template: &str, issue: &Issue) -> Result<String> {
+    let context = Context::from_serialize(IssueTemplateContext {
+        number: issue.number,
+        title: &issue.title,
+        url: &issue.html_url,
+        state: &issue.state,
+        author: &issue.user.login,
+        labels: issue.labels.iter().map(|it| it.name.as_str()).collect(),
+        assignees: issue.assignees.iter().map(|it| it.login.as_str()).collect(),
+        milestone: issue.milestone.as_ref().map(|it| it.title.as_str()),
+    })?;
+
+    Ok(Tera::one_off(template, &context, false)?)
+}
+
+/// Creates the hidden HTML comment appended to a comment body to identify it on re-runs.
+pub(crate) fn create_comment_marker(id: &str) -> String {
+    format!("<!-- renote:comment:{} -->", id)
+}
+
+/// Appends the marker to the rendered comment, or returns None if the issue already has the marker.
+pub(crate) async fn render_issue_comment(
+    repo_component: &(dyn IssueComponentTrait + Send + Sync),
+    issue: &Issue,
+    template: &str,
+    marker: &str,
+) -> Result<Option<String>> {
+    let comments = repo_component.list_comments(issue.number).await?;
+    if comments.iter().any(|it| it.body.contains(marker)) {
+        debug!("issue {} already commented, skipped", issue.number);
+        return Ok(None);
+    }
+
+    let body = render_issue_template(template, issue)?;
+    Ok(Some(format!("{}\n\n{}", body.trim_end(), marker)))
+}
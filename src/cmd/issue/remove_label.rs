@@ -2,12 +2,10 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use clap::{Arg, ArgMatches, Command};
-use libcli_rs::progress::{ProgressBar, ProgressTrait};
 
 use crate::cmd::arg::create_query_arg;
 use crate::cmd::issue::{create_issues_info_to_update, IssueLabelUpdateType};
 use crate::cmd::CommandTrait;
-use crate::component::repo::issue::IssueComponentTrait;
 use crate::component::repo::RepoComponent;
 use crate::config::NoteConfig;
 use crate::result::CmdResult;
@@ -53,15 +51,12 @@ impl CommandTrait for RemoveLabelCommand {
         )
         .await?;
 
-        progress!(
-            format!("Updating issues to remove the labels ({:?})", labels),
-            repo_component.update_issues(&issues_to_update).await?;
-        );
-
-        println!(
-            "Successfully updated issues to remove the labels {:?}",
-            labels
-        );
-        Ok(())
+        self.process_issue_updates(
+            matches,
+            &repo_component,
+            issues_to_update,
+            &format!("to remove the labels {:?}", labels),
+        )
+        .await
     }
 }
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use clap::{ArgMatches, Command};
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+
+use crate::cmd::arg::{create_comment_arg, create_query_arg};
+use crate::cmd::issue::create_issues_info_to_update_state;
+use crate::cmd::CommandTrait;
+use crate::component::repo::issue::IssueComponentTrait;
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::result::CmdResult;
+
+pub const CMD_REOPEN_ISSUE: &str = "reopen";
+
+pub struct ReopenIssueCommand;
+
+impl ReopenIssueCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for ReopenIssueCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_REOPEN_ISSUE)
+            .about("Reopen issues")
+            .args([create_query_arg(), create_comment_arg()])
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        let config = NoteConfig::new(matches);
+        let repo_component = RepoComponent::new(None, Arc::new(config));
+
+        let query = format!(
+            "{} is:closed",
+            matches.value_of("query").unwrap_or_default()
+        );
+        let issues = progress!(
+            "Searching issues",
+            repo_component.search_all_issues_by_query(query.trim_start()).await?;
+        );
+
+        let issues_to_update =
+            create_issues_info_to_update_state(&issues, "open", None, matches.value_of("comment"))?;
+
+        self.process_issue_updates(matches, &repo_component, issues_to_update, "to reopen")
+            .await
+    }
+}
@@ -0,0 +1,203 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use clap::{Arg, ArgMatches, Command};
+use hubcaps_ex::issues::Issue;
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+use log::debug;
+
+use crate::cmd::arg::create_query_arg;
+use crate::cmd::issue::render_issue_template;
+use crate::cmd::CommandTrait;
+use crate::component::repo::issue::{IssueComponentTrait, IssueUpdate};
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::result::{CmdResult, Result};
+
+pub const CMD_STALE_ISSUE: &str = "stale";
+
+const DEFAULT_STALE_LABEL: &str = "lifecycle/stale";
+
+/// The activity within this duration after labeling stale is regarded as the labeling itself.
+const STALE_LABEL_GRACE_SECS: i64 = 60;
+
+pub struct StaleIssueCommand;
+
+struct StaleOptions<'a> {
+    label: &'a str,
+    close_days: i64,
+    close_comment: &'a str,
+}
+
+impl StaleIssueCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for StaleIssueCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_STALE_ISSUE)
+            .about("Label inactive issues as stale, and close the issues staying stale")
+            .args([
+                create_query_arg(),
+                Arg::new("days")
+                    .value_name("number")
+                    .help("Days without activity to label an issue as stale")
+                    .long("days")
+                    .takes_value(true)
+                    .default_value("60"),
+                Arg::new("close-days")
+                    .value_name("number")
+                    .help("Days staying stale to close an issue")
+                    .long("close-days")
+                    .takes_value(true)
+                    .default_value("7"),
+                Arg::new("label")
+                    .value_name("label")
+                    .help("Stale label")
+                    .long("label")
+                    .takes_value(true)
+                    .default_value(DEFAULT_STALE_LABEL),
+                Arg::new("exclude-labels")
+                    .help("Labels of issues never regarded as stale")
+                    .long("exclude-labels")
+                    .value_delimiter(' ')
+                    .takes_value(true),
+                Arg::new("exclude-milestones")
+                    .help("Milestones of issues never regarded as stale")
+                    .long("exclude-milestones")
+                    .value_delimiter(' ')
+                    .takes_value(true),
+                Arg::new("comment")
+                    .value_name("template")
+                    .help("Comment on the issues labeled as stale, which is a Tera template with the issue fields")
+                    .long("comment")
+                    .takes_value(true),
+                Arg::new("close-comment")
+                    .value_name("template")
+                    .help("Comment on the closed stale issues, which is a Tera template with the issue fields")
+                    .long("close-comment")
+                    .takes_value(true),
+            ])
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        let config = NoteConfig::new(matches);
+        let repo_component = RepoComponent::new(None, Arc::new(config));
+
+        let days: i64 = matches.value_of_t("days")?;
+        let close_days: i64 = matches.value_of_t("close-days")?;
+        let label = matches.value_of("label").unwrap();
+        let comment = matches.value_of("comment").map(String::from).unwrap_or_else(|| {
+            format!(
+                "This issue has been labeled as stale because it has had no activity for {} days. It will be closed in {} days if no further activity occurs.",
+                days, close_days
+            )
+        });
+        let close_comment = matches
+            .value_of("close-comment")
+            .map(String::from)
+            .unwrap_or_else(|| {
+                format!(
+                    "This issue has been closed because it has been stale for {} days.",
+                    close_days
+                )
+            });
+
+        let mut query = format!(
+            "{} is:issue is:open",
+            matches.value_of("query").unwrap_or_default()
+        );
+        for label in matches.values_of("exclude-labels").into_iter().flatten() {
+            query.push_str(&format!(" -label:\"{}\"", label));
+        }
+        for milestone in matches
+            .values_of("exclude-milestones")
+            .into_iter()
+            .flatten()
+        {
+            query.push_str(&format!(" -milestone:\"{}\"", milestone));
+        }
+
+        let inactive_issues = progress!(
+            format!("Searching issues inactive for {} days", days),
+            repo_component.search_all_issues_by_query(&format!(
+                "{} -label:\"{}\" updated:<{}",
+                query,
+                label,
+                (Utc::now() - Duration::days(days)).format("%Y-%m-%d")
+            )).await?;
+        );
+        let stale_issues = progress!(
+            "Searching stale issues",
+            repo_component.search_all_issues_by_query(&format!("{} label:\"{}\"", query, label)).await?;
+        );
+
+        let mut issues_to_update = vec![];
+        for issue in &inactive_issues {
+            let mut issue_to_update = IssueUpdate::new(issue);
+            issue_to_update.add_labels(&[label]);
+            issue_to_update.comment = Some(render_issue_template(&comment, issue)?);
+            issues_to_update.push(issue_to_update);
+        }
+
+        let options = StaleOptions {
+            label,
+            close_days,
+            close_comment: &close_comment,
+        };
+        issues_to_update.extend(progress!(
+            "Checking activities of stale issues",
+            create_issues_info_to_update_stale(&repo_component, &stale_issues, &options).await?;
+        ));
+
+        self.process_issue_updates(matches, &repo_component, issues_to_update, "as stale")
+            .await
+    }
+}
+
+/// Removes the stale label if the issue has any activity after labeled, otherwise closes the
+/// issue if it has been stale for the days.
+async fn create_issues_info_to_update_stale(
+    repo_component: &(dyn IssueComponentTrait + Send + Sync),
+    issues: &[Issue],
+    options: &StaleOptions<'_>,
+) -> Result<Vec<IssueUpdate>> {
+    let mut issues_to_update = vec![];
+
+    for issue in issues {
+        let labeled_at = repo_component
+            .list_events_of_issue(issue.number)
+            .await?
+            .into_iter()
+            .filter(|it| it.event == "labeled")
+            .filter(|it| it.label.as_ref().map_or(false, |l| l.name == options.label))
+            .map(|it| it.created_at)
+            .max();
+        let labeled_at = match labeled_at {
+            Some(labeled_at) => DateTime::parse_from_rfc3339(&labeled_at)?.with_timezone(&Utc),
+            None => continue,
+        };
+        let updated_at = DateTime::parse_from_rfc3339(&issue.updated_at)?.with_timezone(&Utc);
+
+        let mut issue_to_update = IssueUpdate::new(issue);
+        if updated_at > labeled_at + Duration::seconds(STALE_LABEL_GRACE_SECS) {
+            debug!(
+                "issue {} has activities since labeled as stale",
+                issue.number
+            );
+            issue_to_update.remove_labels(&[options.label]);
+        } else if Utc::now() > labeled_at + Duration::days(options.close_days) {
+            issue_to_update.after.state = "closed".to_string();
+            issue_to_update.state_reason = Some("not_planned".to_string());
+            issue_to_update.comment = Some(render_issue_template(options.close_comment, issue)?);
+        }
+
+        issues_to_update.push(issue_to_update);
+    }
+
+    Ok(issues_to_update)
+}
@@ -0,0 +1,430 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::stdout;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+use hubcaps_ex::issues::Issue;
+use hubcaps_ex::milestone::{Milestone, MilestoneOptions};
+use libcli_rs::output::{OutputFactory, OutputFormat, OutputTrait};
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::cmd::arg::create_query_arg;
+use crate::cmd::{confirm_issue_updates, CommandTrait};
+use crate::component::repo::issue::{
+    IssueComponentTrait, IssueCreateOptions, IssueMilestone, IssueUpdate,
+};
+use crate::component::repo::label::{Label, LabelComponentTrait};
+use crate::component::repo::milestone::MilestoneComponentTrait;
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::result::{CmdResult, Result};
+use crate::util::get_output_format_from_args;
+
+pub const CMD_TRANSFER_ISSUE: &str = "transfer";
+
+const DEFAULT_LABEL_COLOR: &str = "ededed";
+
+pub struct TransferIssueCommand;
+
+/// The label and milestone mapping from the source repo to the target repo by names.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct TransferMapping {
+    /// The labels mapped to null are dropped.
+    labels: HashMap<String, Option<String>>,
+    milestones: HashMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TransferPlan {
+    repo: String,
+    labels_to_create: Vec<Label>,
+    milestones_to_create: Vec<MilestonePlan>,
+    issues: Vec<IssueTransferPlan>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct MilestonePlan {
+    title: String,
+    description: Option<String>,
+    due_on: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct IssueTransferPlan {
+    number: u64,
+    title: String,
+    labels: Vec<String>,
+    milestone: Option<String>,
+    assignees: Vec<String>,
+}
+
+struct IssueTransferResult {
+    number: u64,
+    target_number: u64,
+    method: String,
+}
+
+impl TransferIssueCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for TransferIssueCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_TRANSFER_ISSUE)
+            .about("Transfer issues to another repo with labels, milestones and assignees")
+            .args([
+                create_query_arg(),
+                Arg::new("to")
+                    .value_name("owner/repo")
+                    .help("Target repo")
+                    .long("to")
+                    .required(true)
+                    .takes_value(true),
+                Arg::new("mapping")
+                    .value_name("file")
+                    .help("Label and milestone mapping yaml file")
+                    .long("mapping")
+                    .takes_value(true),
+                Arg::new("copy")
+                    .help("Create issues in the target repo and close the original ones, instead of transferring")
+                    .long("copy"),
+            ])
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        let target = matches.value_of("to").unwrap();
+        let (target_owner, target_repo) = target
+            .split_once('/')
+            .filter(|(owner, repo)| !owner.is_empty() && !repo.is_empty())
+            .ok_or_else(|| anyhow!("invalid target repo {}, expect owner/repo", target))?;
+
+        let config = NoteConfig::new(matches);
+        let mut target_config = config.clone();
+        target_config.owner = target_owner.to_string();
+        target_config.repo = target_repo.to_string();
+        let repo_component = RepoComponent::new(None, Arc::new(config));
+        let target_component = RepoComponent::new(None, Arc::new(target_config));
+
+        let mapping = match matches.value_of("mapping") {
+            Some(path) => load_transfer_mapping(path)?,
+            None => TransferMapping::default(),
+        };
+
+        let is_console = matches!(get_output_format_from_args(matches)?, OutputFormat::Console);
+        let query = format!("{} is:issue", matches.value_of("query").unwrap_or_default());
+        let issues = progress!(
+            "Searching issues",
+            repo_component.search_all_issues_by_query(&query).await?;
+        );
+        if issues.is_empty() {
+            if !is_console {
+                return print_transfer_plan(
+                    matches,
+                    &create_transfer_plan(target, &[], &mapping, &[], &[], &[]),
+                );
+            }
+            println!("No issues to transfer");
+            return Ok(());
+        }
+
+        let labels = progress!("Listing labels", repo_component.list_labels().await?;);
+        let target_labels = progress!(
+            format!("Listing labels of {}", target),
+            target_component.list_labels().await?;
+        );
+        let target_milestones = progress!(
+            format!("Listing milestones of {}", target),
+            target_component.list_milestones("all").await?;
+        );
+
+        let plan = create_transfer_plan(
+            target,
+            &issues,
+            &mapping,
+            &labels,
+            &target_labels,
+            &target_milestones,
+        );
+        print_transfer_plan(matches, &plan)?;
+
+        if matches.is_present("dry-run") {
+            return Ok(());
+        }
+
+        confirm_issue_updates(
+            matches,
+            plan.issues.len(),
+            &format!("to transfer to {}", target),
+        )?;
+
+        for label in &plan.labels_to_create {
+            progress!(
+                format!("Creating the label {} in {}", label.name, target),
+                target_component.create_label(label).await?;
+            );
+        }
+
+        let mut milestones: HashMap<String, u64> = target_milestones
+            .iter()
+            .map(|it| (it.title.clone(), it.number))
+            .collect();
+        for milestone in &plan.milestones_to_create {
+            let options = MilestoneOptions {
+                title: milestone.title.clone(),
+                state: "open".to_string(),
+                description: milestone.description.clone(),
+                due_on: milestone.due_on.clone(),
+            };
+            let milestone = progress!(
+                format!("Creating the milestone {} in {}", milestone.title, target),
+                target_component.create_milestone(&options).await?;
+            );
+            milestones.insert(milestone.title, milestone.number);
+        }
+
+        let mut results = vec![];
+        for (issue, issue_plan) in issues.iter().zip(&plan.issues) {
+            let milestone = issue_plan.milestone.as_ref().map(|title| IssueMilestone {
+                number: milestones[title],
+                title: title.clone(),
+            });
+
+            let transferred_number = if matches.is_present("copy") {
+                None
+            } else {
+                // only fall back to copy if refused, as the transfer may be done on other errors
+                let number = progress!(
+                    format!("Transferring the issue {} to {}", issue.number, target),
+                    repo_component.transfer_issue(issue.number, target_owner, target_repo).await?;
+                );
+                if number.is_none() {
+                    warn!(
+                        "issue {} not transferred, falling back to copy",
+                        issue.number
+                    );
+                }
+
+                number
+            };
+
+            let result = match transferred_number {
+                Some(number) => {
+                    // transferring keeps only the labels and assignees existing in the target repo
+                    let transferred_issue = target_component.get_issue(number).await?;
+                    let mut issue_to_update = IssueUpdate::new(&transferred_issue);
+                    issue_to_update.after.labels = issue_plan.labels.clone();
+                    issue_to_update.after.milestone = milestone;
+                    issue_to_update.after.assignees = issue_plan.assignees.clone();
+
+                    progress!(
+                        format!("Updating the issue {}#{}", target, number),
+                        target_component.update_issue(&issue_to_update).await?;
+                    );
+
+                    IssueTransferResult::transferred(issue.number, number)
+                }
+                None => {
+                    copy_issue(
+                        &repo_component,
+                        &target_component,
+                        issue,
+                        issue_plan,
+                        milestone,
+                        target,
+                    )
+                    .await?
+                }
+            };
+            results.push(result);
+        }
+
+        if is_console {
+            print_transfer_results(target, &results);
+        }
+        Ok(())
+    }
+}
+
+impl IssueTransferResult {
+    fn transferred(number: u64, target_number: u64) -> Self {
+        IssueTransferResult {
+            number,
+            target_number,
+            method: "transfer".to_string(),
+        }
+    }
+}
+
+/// Creates the issue in the target repo, then closes the original issue with a comment linking it.
+async fn copy_issue(
+    repo_component: &(dyn IssueComponentTrait + Send + Sync),
+    target_component: &(dyn IssueComponentTrait + Send + Sync),
+    issue: &Issue,
+    issue_plan: &IssueTransferPlan,
+    milestone: Option<IssueMilestone>,
+    target: &str,
+) -> Result<IssueTransferResult> {
+    let (owner, repo) = repo_component.repo_name();
+
+    let created_issue = progress!(
+        format!("Creating the copy of the issue {} in {}", issue.number, target),
+        target_component.create_issue(&IssueCreateOptions {
+            title: issue.title.clone(),
+            body: Some(format!(
+                "{}\n\nMoved from {}/{}#{}",
+                issue.body.as_deref().unwrap_or_default().trim_end(),
+                owner,
+                repo,
+                issue.number
+            )),
+            labels: issue_plan.labels.clone(),
+            assignees: issue_plan.assignees.clone(),
+            milestone: milestone.map(|it| it.number),
+        }).await?;
+    );
+
+    let mut issue_to_update = IssueUpdate::new(issue);
+    issue_to_update.after.state = "closed".to_string();
+    issue_to_update.state_reason = Some("not_planned".to_string());
+    issue_to_update.comment = Some(format!("Moved to {}#{}", target, created_issue.number));
+
+    progress!(
+        format!("Closing the issue {}", issue.number),
+        repo_component.update_issue(&issue_to_update).await?;
+    );
+
+    Ok(IssueTransferResult {
+        number: issue.number,
+        target_number: created_issue.number,
+        method: "copy".to_string(),
+    })
+}
+
+fn load_transfer_mapping(path: &str) -> Result<TransferMapping> {
+    let file = File::open(path).map_err(|err| anyhow!("failed to open {}: {}", path, err))?;
+
+    serde_yaml::from_reader(file).map_err(|err| anyhow!("failed to parse {}: {}", path, err))
+}
+
+fn create_transfer_plan(
+    target: &str,
+    issues: &[Issue],
+    mapping: &TransferMapping,
+    labels: &[Label],
+    target_labels: &[Label],
+    target_milestones: &[Milestone],
+) -> TransferPlan {
+    let mut plan = TransferPlan {
+        repo: target.to_string(),
+        labels_to_create: vec![],
+        milestones_to_create: vec![],
+        issues: vec![],
+    };
+
+    for issue in issues {
+        let mut issue_labels = vec![];
+        for label in &issue.labels {
+            let name = match mapping.labels.get(&label.name) {
+                Some(Some(name)) => name.clone(),
+                Some(None) => continue,
+                None => label.name.clone(),
+            };
+            if issue_labels.contains(&name) {
+                continue;
+            }
+
+            let exists = target_labels
+                .iter()
+                .chain(&plan.labels_to_create)
+                .any(|it| it.name.to_lowercase() == name.to_lowercase());
+            if !exists {
+                let label = labels.iter().find(|it| it.name == label.name);
+                plan.labels_to_create.push(Label {
+                    name: name.clone(),
+                    color: label.map_or(DEFAULT_LABEL_COLOR.to_string(), |it| it.color.clone()),
+                    description: label.and_then(|it| it.description.clone()),
+                });
+            }
+
+            issue_labels.push(name);
+        }
+
+        let milestone = issue.milestone.as_ref().map(|milestone| {
+            let title = mapping
+                .milestones
+                .get(&milestone.title)
+                .cloned()
+                .unwrap_or_else(|| milestone.title.clone());
+
+            let exists = target_milestones.iter().any(|it| it.title == title)
+                || plan.milestones_to_create.iter().any(|it| it.title == title);
+            if !exists {
+                plan.milestones_to_create.push(MilestonePlan {
+                    title: title.clone(),
+                    description: milestone.description.clone(),
+                    due_on: milestone.due_on.clone(),
+                });
+            }
+
+            title
+        });
+
+        plan.issues.push(IssueTransferPlan {
+            number: issue.number,
+            title: issue.title.clone(),
+            labels: issue_labels,
+            milestone,
+            assignees: issue.assignees.iter().map(|it| it.login.clone()).collect(),
+        });
+    }
+
+    plan
+}
+
+fn print_transfer_plan(matches: &ArgMatches, plan: &TransferPlan) -> CmdResult {
+    let output_format = get_output_format_from_args(matches)?;
+    if !matches!(output_format, OutputFormat::Console) {
+        return output!(output_format, .display(stdout(), plan, None, None));
+    }
+
+    for label in &plan.labels_to_create {
+        println!("+ label {} (#{})", label.name, label.color);
+    }
+    for milestone in &plan.milestones_to_create {
+        println!("+ milestone {}", milestone.title);
+    }
+    for issue in &plan.issues {
+        println!("#{} {} -> {}", issue.number, issue.title, plan.repo);
+        println!("  labels:    {:?}", issue.labels);
+        println!(
+            "  milestone: {}",
+            issue.milestone.as_deref().unwrap_or("N/A")
+        );
+        println!("  assignees: {:?}", issue.assignees);
+    }
+
+    Ok(())
+}
+
+fn print_transfer_results(target: &str, results: &[IssueTransferResult]) {
+    for result in results {
+        println!(
+            "#{} -> {}#{} ({})",
+            result.number, target, result.target_number, result.method
+        );
+    }
+    println!(
+        "Successfully transferred {} issues to {}",
+        results.len(),
+        target
+    );
+}
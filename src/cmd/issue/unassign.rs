@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+
+use crate::cmd::arg::{create_query_arg, create_users_arg};
+use crate::cmd::CommandTrait;
+use crate::component::repo::issue::{IssueComponentTrait, IssueUpdate};
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::result::CmdResult;
+
+pub const CMD_UNASSIGN_ISSUE: &str = "unassign";
+
+pub struct UnassignIssueCommand;
+
+impl UnassignIssueCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for UnassignIssueCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_UNASSIGN_ISSUE)
+            .about("Unassign users from issues")
+            .args([
+                create_query_arg(),
+                create_users_arg()
+                    .conflicts_with("all")
+                    .required_unless_present("all"),
+                Arg::new("all").help("Unassign all users").long("all"),
+            ])
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        let config = NoteConfig::new(matches);
+        let repo_component = RepoComponent::new(None, Arc::new(config));
+        let users: Vec<_> = matches
+            .values_of("users")
+            .map(|it| it.collect())
+            .unwrap_or_default();
+
+        let issues = progress!(
+            "Searching issues",
+            repo_component.search_all_issues_by_query(matches.value_of("query").unwrap_or_default()).await?;
+        );
+
+        let issues_to_update = issues
+            .iter()
+            .map(|it| {
+                let mut issue = IssueUpdate::new(it);
+                if matches.is_present("all") {
+                    issue.after.assignees.clear();
+                } else {
+                    issue.remove_assignees(&users);
+                }
+                issue
+            })
+            .collect();
+
+        let action = if matches.is_present("all") {
+            "to unassign all users".to_string()
+        } else {
+            format!("to unassign {:?}", users)
+        };
+
+        self.process_issue_updates(matches, &repo_component, issues_to_update, &action)
+            .await
+    }
+}
@@ -2,10 +2,10 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use clap::{ArgMatches, Command};
-use hubcaps_ex::issues::IssueOptions;
 use libcli_rs::progress::{ProgressBar, ProgressTrait};
 
 use crate::cmd::arg::create_query_arg;
+use crate::cmd::issue::create_issues_info_to_update_milestone;
 use crate::cmd::{CmdResult, CommandTrait};
 use crate::component::repo::issue::IssueComponentTrait;
 use crate::component::repo::RepoComponent;
@@ -39,30 +39,14 @@ impl CommandTrait for UnassignMilestoneCommand {
             repo_component.search_issues_by_query(matches.value_of("query").unwrap_or_default()).await?;
         );
 
-        let issues_to_update: Vec<_> = issues
-            .into_iter()
-            .map(|it| {
-                (
-                    it.number,
-                    IssueOptions {
-                        title: it.title,
-                        body: it.body,
-                        assignee: None,
-                        assignees: Some(it.assignees.into_iter().map(|it| it.login).collect()),
-                        milestone: Some(0),
-                        labels: it.labels.into_iter().map(|it| it.name).collect(),
-                        state: it.state,
-                    },
-                )
-            })
-            .collect();
+        let issues_to_update = create_issues_info_to_update_milestone(&issues, None);
 
-        progress!(
-            "Updating issues out from the original milestone",
-            repo_component.update_issues(&issues_to_update).await?;
-        );
-
-        println!("Successfully updated issues out from the original milestone");
-        Ok(())
+        self.process_issue_updates(
+            matches,
+            &repo_component,
+            issues_to_update,
+            "out from the original milestone",
+        )
+        .await
     }
 }
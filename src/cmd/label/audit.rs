@@ -0,0 +1,232 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::stdout;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+use hubcaps_ex::issues::Issue;
+use libcli_rs::output::{OutputFactory, OutputFormat, OutputTrait};
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+use serde::{Deserialize, Serialize};
+
+use crate::cmd::arg::create_query_arg;
+use crate::cmd::label::is_label_of_family;
+use crate::cmd::CommandTrait;
+use crate::component::repo::issue::IssueComponentTrait;
+use crate::component::repo::label::LabelComponentTrait;
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::result::{CmdResult, Result};
+use crate::util::get_output_format_from_args;
+
+pub const CMD_AUDIT_LABEL: &str = "audit";
+
+const DEFAULT_AUDIT_QUERY: &str = "is:issue is:open";
+
+pub struct AuditLabelCommand;
+
+/// The label policy to audit issues against. A label family is a label name, or a prefix like `kind/*`.
+#[derive(Debug, Default, Deserialize)]
+struct LabelPolicy {
+    /// The query of the issues to audit, `is:issue is:open` by default.
+    #[serde(default)]
+    query: Option<String>,
+    /// The label families of which each issue has to have at least one label.
+    #[serde(default)]
+    required: Vec<String>,
+    /// The label families of which each issue can have at most one label.
+    #[serde(default)]
+    exclusive: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LabelAuditReport {
+    issue_count: usize,
+    missing_labels: Vec<IssueLabelProblem>,
+    conflicting_labels: Vec<IssueLabelProblem>,
+    unused_labels: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct IssueLabelProblem {
+    number: u64,
+    title: String,
+    url: String,
+    family: String,
+    labels: Vec<String>,
+}
+
+impl LabelAuditReport {
+    fn problem_count(&self) -> usize {
+        self.missing_labels.len() + self.conflicting_labels.len() + self.unused_labels.len()
+    }
+}
+
+impl AuditLabelCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for AuditLabelCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_AUDIT_LABEL)
+            .about("Audit issue labels against a label policy yaml file")
+            .args([
+                create_query_arg(),
+                Arg::new("policy")
+                    .value_name("file")
+                    .help("Label policy yaml file")
+                    .long("policy")
+                    .required(true)
+                    .takes_value(true),
+            ])
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        let config = NoteConfig::new(matches);
+        let repo_component = RepoComponent::new(None, Arc::new(config));
+
+        let policy = load_label_policy(matches.value_of("policy").unwrap())?;
+        let query = matches
+            .value_of("query")
+            .or(policy.query.as_deref())
+            .unwrap_or(DEFAULT_AUDIT_QUERY);
+
+        let issues = progress!(
+            "Searching issues",
+            repo_component.search_all_issues_by_query(query).await?;
+        );
+        let labels = progress!("Listing labels", repo_component.list_labels().await?;);
+
+        // count the label usage by a single listing instead of a search per label
+        let all_issues = progress!(
+            "Listing all issues",
+            repo_component.list_all_issues(&[]).await?;
+        );
+        let used_labels: HashSet<_> = all_issues
+            .iter()
+            .flat_map(|issue| issue.labels.iter().map(|it| it.name.as_str()))
+            .collect();
+        let unused_labels = labels
+            .into_iter()
+            .filter(|it| !used_labels.contains(it.name.as_str()))
+            .map(|it| it.name)
+            .collect();
+
+        let report = LabelAuditReport {
+            issue_count: issues.len(),
+            missing_labels: issues
+                .iter()
+                .flat_map(|issue| find_missing_labels(&policy, issue))
+                .collect(),
+            conflicting_labels: issues
+                .iter()
+                .flat_map(|issue| find_conflicting_labels(&policy, issue))
+                .collect(),
+            unused_labels,
+        };
+
+        let output_format = get_output_format_from_args(matches)?;
+        if let OutputFormat::Console = output_format {
+            print_report(&report);
+        } else {
+            output!(output_format, .display(stdout(), &report, None, None))?;
+        }
+
+        if report.problem_count() > 0 {
+            return Err(anyhow!("{} label problems found", report.problem_count()));
+        }
+
+        Ok(())
+    }
+}
+
+fn load_label_policy(path: &str) -> Result<LabelPolicy> {
+    let file = File::open(path).map_err(|err| anyhow!("failed to open {}: {}", path, err))?;
+
+    serde_yaml::from_reader(file).map_err(|err| anyhow!("failed to parse {}: {}", path, err))
+}
+
+fn create_issue_label_problem(
+    issue: &Issue,
+    family: &str,
+    labels: Vec<String>,
+) -> IssueLabelProblem {
+    IssueLabelProblem {
+        number: issue.number,
+        title: issue.title.clone(),
+        url: issue.html_url.clone(),
+        family: family.to_string(),
+        labels,
+    }
+}
+
+fn find_missing_labels(policy: &LabelPolicy, issue: &Issue) -> Vec<IssueLabelProblem> {
+    policy
+        .required
+        .iter()
+        .filter(|family| {
+            !issue
+                .labels
+                .iter()
+                .any(|it| is_label_of_family(&it.name, family))
+        })
+        .map(|family| create_issue_label_problem(issue, family, vec![]))
+        .collect()
+}
+
+fn find_conflicting_labels(policy: &LabelPolicy, issue: &Issue) -> Vec<IssueLabelProblem> {
+    policy
+        .exclusive
+        .iter()
+        .filter_map(|family| {
+            let labels: Vec<_> = issue
+                .labels
+                .iter()
+                .filter(|it| is_label_of_family(&it.name, family))
+                .map(|it| it.name.clone())
+                .collect();
+
+            if labels.len() > 1 {
+                Some(create_issue_label_problem(issue, family, labels))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn print_report(report: &LabelAuditReport) {
+    println!("Audited issues: {}", report.issue_count);
+
+    println!(
+        "\nIssues missing required labels: {}",
+        report.missing_labels.len()
+    );
+    for problem in &report.missing_labels {
+        println!(
+            "  - #{} {} ({}): no {}",
+            problem.number, problem.title, problem.url, problem.family
+        );
+    }
+
+    println!(
+        "\nIssues with conflicting labels: {}",
+        report.conflicting_labels.len()
+    );
+    for problem in &report.conflicting_labels {
+        println!(
+            "  - #{} {} ({}): {:?}",
+            problem.number, problem.title, problem.url, problem.labels
+        );
+    }
+
+    println!("\nUnused labels: {}", report.unused_labels.len());
+    for label in &report.unused_labels {
+        println!("  - {}", label);
+    }
+}
@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+
+use crate::checkpoint::PostStep;
+use crate::cmd::{update_issues, CommandTrait, IssueUpdateOptions};
+use crate::component::repo::issue::{IssueComponentTrait, IssueUpdate};
+use crate::component::repo::label::{LabelComponentTrait, LabelUpdateOptions};
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::result::CmdResult;
+
+pub const CMD_MERGE_LABEL: &str = "merge";
+
+pub struct MergeLabelCommand;
+
+impl MergeLabelCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for MergeLabelCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_MERGE_LABEL)
+            .about("Move issues from a label to another label, then delete the label")
+            .args([
+                Arg::new("label")
+                    .value_name("label")
+                    .help("Label to merge and delete")
+                    .required(true)
+                    .takes_value(true),
+                Arg::new("target-label")
+                    .value_name("target-label")
+                    .help("Label to merge into")
+                    .required(true)
+                    .takes_value(true),
+            ])
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        let config = NoteConfig::new(matches);
+        let repo_component = RepoComponent::new(None, Arc::new(config));
+        let name = matches.value_of("label").unwrap();
+        let target_name = matches.value_of("target-label").unwrap();
+
+        let label = repo_component
+            .find_label(name)
+            .await?
+            .ok_or_else(|| anyhow!("label {} not found", name))?;
+
+        let target_label = match repo_component.find_label(target_name).await? {
+            Some(target_label) => target_label,
+            None => {
+                // nothing to merge into, so simply rename the label in place
+                if matches.is_present("dry-run") {
+                    println!("~ {} -> {}", label.name, target_name);
+                    return Ok(());
+                }
+
+                progress!(
+                    format!("Renaming the label {} to {}", label.name, target_name),
+                    repo_component.update_label(&label.name, &LabelUpdateOptions {
+                        new_name: Some(target_name.to_string()),
+                        ..Default::default()
+                    }).await?;
+                );
+
+                println!(
+                    "Successfully renamed the label {} to {}",
+                    label.name, target_name
+                );
+                return Ok(());
+            }
+        };
+
+        let issues = progress!(
+            format!("Listing issues of the label {}", label.name),
+            repo_component.list_all_issues(&[label.name.as_str()]).await?;
+        );
+        let issues_to_update = issues
+            .iter()
+            .map(|it| {
+                let mut issue = IssueUpdate::new(it);
+                issue.remove_labels(&[label.name.as_str()]);
+                issue.add_labels(&[target_label.name.as_str()]);
+                issue
+            })
+            .collect();
+
+        // the label is deleted after all issues are moved, also on resume
+        update_issues(
+            matches,
+            &repo_component,
+            issues_to_update,
+            &format!("from the label {} to {}", label.name, target_label.name),
+            IssueUpdateOptions {
+                post_step: Some(PostStep::DeleteLabel {
+                    name: label.name.clone(),
+                }),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        if matches.is_present("dry-run") {
+            println!("- {}", label.name);
+            return Ok(());
+        }
+
+        println!(
+            "Successfully merged the label {} into {}",
+            label.name, target_label.name
+        );
+        Ok(())
+    }
+}
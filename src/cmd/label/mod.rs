@@ -0,0 +1,56 @@
+use async_trait::async_trait;
+
+pub use audit::{AuditLabelCommand, CMD_AUDIT_LABEL};
+pub use merge::{MergeLabelCommand, CMD_MERGE_LABEL};
+pub use rename::{RenameLabelCommand, CMD_RENAME_LABEL};
+pub use sync::{SyncLabelCommand, CMD_SYNC_LABEL};
+
+use crate::cmd::{create_cmd, CommandSetting, CommandTrait};
+
+mod audit;
+mod merge;
+mod rename;
+mod sync;
+
+pub const CMD_LABEL: &str = "label";
+
+pub struct LabelCommand {
+    setting: CommandSetting,
+}
+
+impl LabelCommand {
+    pub fn new() -> Self {
+        LabelCommand {
+            setting: CommandSetting {
+                name: CMD_LABEL,
+                about: "Label commands",
+                commands: hashmap! {
+                    CMD_SYNC_LABEL => create_cmd(Box::new(SyncLabelCommand::new())),
+                    CMD_RENAME_LABEL => create_cmd(Box::new(RenameLabelCommand::new())),
+                    CMD_MERGE_LABEL => create_cmd(Box::new(MergeLabelCommand::new())),
+                    CMD_AUDIT_LABEL => create_cmd(Box::new(AuditLabelCommand::new())),
+                },
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl CommandTrait for LabelCommand {
+    fn setting(&self) -> &CommandSetting {
+        &self.setting
+    }
+}
+
+/// Compares label names case-insensitively as GitHub does.
+pub(crate) fn is_same_label_name(name: &str, other_name: &str) -> bool {
+    name.to_lowercase() == other_name.to_lowercase()
+}
+
+/// Checks if the label belongs to the label family, which is a label name or a prefix like `kind/*`.
+pub(crate) fn is_label_of_family(label: &str, family: &str) -> bool {
+    match family.strip_suffix('*') {
+        Some(prefix) => label.to_lowercase().starts_with(&prefix.to_lowercase()),
+        None => is_same_label_name(label, family),
+    }
+}
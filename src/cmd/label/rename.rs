@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+
+use crate::cmd::CommandTrait;
+use crate::component::repo::label::{LabelComponentTrait, LabelUpdateOptions};
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::result::CmdResult;
+
+pub const CMD_RENAME_LABEL: &str = "rename";
+
+pub struct RenameLabelCommand;
+
+impl RenameLabelCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for RenameLabelCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_RENAME_LABEL)
+            .about("Rename a label in place, so the labeled issues keep the label")
+            .args([
+                Arg::new("label")
+                    .value_name("label")
+                    .help("Label")
+                    .required(true)
+                    .takes_value(true),
+                Arg::new("new-label")
+                    .value_name("new-label")
+                    .help("New label name")
+                    .required(true)
+                    .takes_value(true),
+            ])
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        let config = NoteConfig::new(matches);
+        let repo_component = RepoComponent::new(None, Arc::new(config));
+        let name = matches.value_of("label").unwrap();
+        let new_name = matches.value_of("new-label").unwrap();
+
+        let label = repo_component
+            .find_label(name)
+            .await?
+            .ok_or_else(|| anyhow!("label {} not found", name))?;
+
+        if matches.is_present("dry-run") {
+            println!("~ {} -> {}", label.name, new_name);
+            return Ok(());
+        }
+
+        progress!(
+            format!("Renaming the label {} to {}", label.name, new_name),
+            repo_component.update_label(&label.name, &LabelUpdateOptions {
+                new_name: Some(new_name.to_string()),
+                ..Default::default()
+            }).await?;
+        );
+
+        println!(
+            "Successfully renamed the label {} to {}",
+            label.name, new_name
+        );
+        Ok(())
+    }
+}
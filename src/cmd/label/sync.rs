@@ -0,0 +1,264 @@
+use std::fs::File;
+use std::io::stdout;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+use libcli_rs::output::{OutputFactory, OutputFormat, OutputTrait};
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+use serde::{Deserialize, Serialize};
+
+use crate::cmd::label::is_same_label_name;
+use crate::cmd::{confirm, CommandTrait};
+use crate::component::repo::label::{Label, LabelComponentTrait, LabelUpdateOptions};
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::result::{CmdResult, Result};
+use crate::util::get_output_format_from_args;
+
+pub const CMD_SYNC_LABEL: &str = "sync";
+
+pub struct SyncLabelCommand;
+
+/// The desired labels of a repo, usually maintained in a yaml file.
+#[derive(Debug, Deserialize)]
+struct LabelDefinitions {
+    labels: Vec<LabelDefinition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LabelDefinition {
+    name: String,
+    color: String,
+    #[serde(default)]
+    description: Option<String>,
+    /// The previous names of the label, which are renamed in place to keep the labeled issues.
+    #[serde(default)]
+    aliases: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LabelSyncPlan {
+    changes: Vec<LabelChange>,
+    undefined_labels: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "lowercase")]
+enum LabelChange {
+    Create { label: Label },
+    Update { name: String, label: Label },
+    Delete { name: String },
+}
+
+impl SyncLabelCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for SyncLabelCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_SYNC_LABEL)
+            .about("Sync repo labels from a label definition yaml file")
+            .args([
+                Arg::new("file")
+                    .value_name("file")
+                    .help("Label definition yaml file")
+                    .long("file")
+                    .required(true)
+                    .takes_value(true),
+                Arg::new("delete")
+                    .help("Delete the labels not defined in the file")
+                    .long("delete"),
+            ])
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        let config = NoteConfig::new(matches);
+        let repo_component = RepoComponent::new(None, Arc::new(config));
+
+        let definitions = load_label_definitions(matches.value_of("file").unwrap())?;
+        let labels = progress!("Listing labels", repo_component.list_labels().await?;);
+
+        let (changes, undefined_labels) =
+            create_label_changes(definitions, labels, matches.is_present("delete"));
+        let plan = LabelSyncPlan {
+            changes,
+            undefined_labels,
+        };
+
+        print_label_sync_plan(matches, &plan)?;
+
+        let changes = plan.changes;
+        if changes.is_empty() || matches.is_present("dry-run") {
+            return Ok(());
+        }
+
+        confirm(
+            matches,
+            &format!("{} label changes will be applied", changes.len()),
+        )?;
+
+        for change in &changes {
+            match change {
+                LabelChange::Create { label } => {
+                    progress!(
+                        format!("Creating the label {}", label.name),
+                        repo_component.create_label(label).await?;
+                    );
+                }
+                LabelChange::Update { name, label } => {
+                    progress!(
+                        format!("Updating the label {}", name),
+                        repo_component.update_label(name, &LabelUpdateOptions {
+                            new_name: Some(label.name.clone()),
+                            color: Some(label.color.clone()),
+                            description: label.description.clone(),
+                        }).await?;
+                    );
+                }
+                LabelChange::Delete { name } => {
+                    progress!(
+                        format!("Deleting the label {}", name),
+                        repo_component.delete_label(name).await?;
+                    );
+                }
+            }
+        }
+
+        let output_format = get_output_format_from_args(matches)?;
+        if matches!(output_format, OutputFormat::Console) {
+            println!("Successfully synced {} labels", changes.len());
+        }
+        Ok(())
+    }
+}
+
+fn load_label_definitions(path: &str) -> Result<Vec<LabelDefinition>> {
+    let file = File::open(path).map_err(|err| anyhow!("failed to open {}: {}", path, err))?;
+    let definitions: LabelDefinitions = serde_yaml::from_reader(file)
+        .map_err(|err| anyhow!("failed to parse {}: {}", path, err))?;
+
+    Ok(definitions.labels)
+}
+
+/// Diffs the desired labels against the repo labels, and returns the changes and the undefined labels.
+fn create_label_changes(
+    definitions: Vec<LabelDefinition>,
+    mut labels: Vec<Label>,
+    delete: bool,
+) -> (Vec<LabelChange>, Vec<String>) {
+    fn take_label(labels: &mut Vec<Label>, name: &str) -> Option<Label> {
+        labels
+            .iter()
+            .position(|it| is_same_label_name(&it.name, name))
+            .map(|index| labels.remove(index))
+    }
+
+    let mut changes = vec![];
+    let mut definitions_to_rename = vec![];
+
+    // match the names first, so an alias never takes the label defined by another name
+    for definition in definitions {
+        match take_label(&mut labels, &definition.name) {
+            Some(label) => changes.extend(create_label_update(label, definition)),
+            None => definitions_to_rename.push(definition),
+        }
+    }
+
+    for definition in definitions_to_rename {
+        let label = definition
+            .aliases
+            .iter()
+            .find_map(|alias| take_label(&mut labels, alias));
+
+        match label {
+            Some(label) => changes.extend(create_label_update(label, definition)),
+            None => changes.push(LabelChange::Create {
+                label: Label {
+                    name: definition.name,
+                    color: normalize_color(&definition.color),
+                    description: definition.description,
+                },
+            }),
+        }
+    }
+
+    let undefined_labels = labels.into_iter().map(|it| it.name).collect();
+    if !delete {
+        return (changes, undefined_labels);
+    }
+
+    changes.extend(
+        undefined_labels
+            .into_iter()
+            .map(|name| LabelChange::Delete { name }),
+    );
+
+    (changes, vec![])
+}
+
+fn create_label_update(label: Label, definition: LabelDefinition) -> Option<LabelChange> {
+    let desired_label = Label {
+        name: definition.name,
+        color: normalize_color(&definition.color),
+        description: Some(definition.description.unwrap_or_default()),
+    };
+
+    if label.name == desired_label.name
+        && normalize_color(&label.color) == desired_label.color
+        && label.description.as_deref().unwrap_or_default()
+            == desired_label.description.as_deref().unwrap_or_default()
+    {
+        return None;
+    }
+
+    Some(LabelChange::Update {
+        name: label.name,
+        label: desired_label,
+    })
+}
+
+fn normalize_color(color: &str) -> String {
+    color.trim_start_matches('#').to_lowercase()
+}
+
+fn print_label_sync_plan(matches: &ArgMatches, plan: &LabelSyncPlan) -> CmdResult {
+    let output_format = get_output_format_from_args(matches)?;
+    if !matches!(output_format, OutputFormat::Console) {
+        return output!(output_format, .display(stdout(), plan, None, None));
+    }
+
+    fn label_str(label: &Label) -> String {
+        format!(
+            "{} (#{}) {}",
+            label.name,
+            label.color,
+            label.description.as_deref().unwrap_or_default()
+        )
+    }
+
+    for change in &plan.changes {
+        match change {
+            LabelChange::Create { label } => println!("+ {}", label_str(label)),
+            LabelChange::Update { name, label } if *name != label.name => {
+                println!("~ {} -> {}", name, label_str(label))
+            }
+            LabelChange::Update { label, .. } => println!("~ {}", label_str(label)),
+            LabelChange::Delete { name } => println!("- {}", name),
+        }
+    }
+
+    if !plan.undefined_labels.is_empty() {
+        println!(
+            "{} labels not defined in the file, use --delete to delete them: {:?}",
+            plan.undefined_labels.len(),
+            plan.undefined_labels
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,186 @@
+use std::io::stdout;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+use hubcaps_ex::issues::Issue;
+use libcli_rs::output::{OutputFactory, OutputFormat, OutputTrait};
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+use serde::{Deserialize, Serialize};
+
+use crate::cmd::CommandTrait;
+use crate::component::repo::issue::IssueComponentTrait;
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::result::CmdResult;
+use crate::util::{create_github_client, get_output_format_from_args};
+
+pub const CMD_CHECK_MILESTONE: &str = "check";
+
+pub struct CheckMilestoneCommand;
+
+#[derive(Serialize, Deserialize)]
+struct MilestoneCheckReport {
+    milestone: String,
+    ready: bool,
+    blocking_issues: Vec<IssueSummary>,
+    unverified_issues: Vec<IssueSummary>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct IssueSummary {
+    number: u64,
+    title: String,
+    url: String,
+    labels: Vec<String>,
+}
+
+impl From<Issue> for IssueSummary {
+    fn from(issue: Issue) -> Self {
+        IssueSummary {
+            number: issue.number,
+            title: issue.title,
+            url: issue.html_url,
+            labels: issue.labels.into_iter().map(|it| it.name).collect(),
+        }
+    }
+}
+
+impl CheckMilestoneCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for CheckMilestoneCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_CHECK_MILESTONE)
+            .about("Check if a milestone is ready to release")
+            .visible_alias("c")
+            .args([
+                Arg::new("milestone")
+                    .value_name("milestone")
+                    .help("Milestone")
+                    .required(true)
+                    .takes_value(true),
+                Arg::new("blocking-labels")
+                    .help("Labels blocking the release if any open issue has them")
+                    .long("blocking-labels")
+                    .value_delimiter(' ')
+                    .default_value("priority/0 release-blocker")
+                    .takes_value(true),
+                Arg::new("required-labels")
+                    .help("Labels required on all closed issues, e.g. qa/verified")
+                    .long("required-labels")
+                    .value_delimiter(' ')
+                    .takes_value(true),
+            ])
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        let milestone = matches.value_of("milestone").unwrap();
+        let blocking_labels: Vec<_> = matches.values_of("blocking-labels").unwrap().collect();
+        let required_labels: Vec<String> = matches
+            .values_of("required-labels")
+            .map(|it| it.map(String::from).collect())
+            .unwrap_or_default();
+
+        let github = Arc::new(create_github_client(matches.value_of("token").unwrap())?);
+
+        let mut config = NoteConfig::new(matches);
+        config.milestone = Some(milestone.to_string());
+        config.state = "open".to_string();
+        let repo_component = RepoComponent::new(Some(github.clone()), Arc::new(config.clone()));
+
+        repo_component.get_milestone(milestone).await?;
+
+        let open_issues = progress!(
+            "Searching open issues",
+            repo_component.search_issues_by_query("is:issue").await?;
+        );
+        let blocking_issues: Vec<IssueSummary> = open_issues
+            .into_iter()
+            .filter(|it| {
+                it.labels
+                    .iter()
+                    .any(|l| blocking_labels.contains(&l.name.as_str()))
+            })
+            .map(IssueSummary::from)
+            .collect();
+
+        let mut unverified_issues: Vec<IssueSummary> = vec![];
+        if !required_labels.is_empty() {
+            config.state = "closed".to_string();
+            let repo_component = RepoComponent::new(Some(github), Arc::new(config));
+
+            let closed_issues = progress!(
+                "Searching closed issues",
+                repo_component.search_issues_by_query("is:issue").await?;
+            );
+            unverified_issues = closed_issues
+                .into_iter()
+                .filter(|it| {
+                    !required_labels
+                        .iter()
+                        .all(|l| it.labels.iter().any(|il| il.name == *l))
+                })
+                .map(IssueSummary::from)
+                .collect();
+        }
+
+        let report = MilestoneCheckReport {
+            milestone: milestone.to_string(),
+            ready: blocking_issues.is_empty() && unverified_issues.is_empty(),
+            blocking_issues,
+            unverified_issues,
+        };
+
+        let output_format = get_output_format_from_args(matches)?;
+        if let OutputFormat::Console = output_format {
+            print_report(&report, &blocking_labels, &required_labels);
+        } else {
+            output!(output_format, .display(stdout(), &report, None, None))?;
+        }
+
+        if !report.ready {
+            return Err(anyhow!("milestone {} is not ready to release", milestone));
+        }
+
+        Ok(())
+    }
+}
+
+fn print_report(
+    report: &MilestoneCheckReport,
+    blocking_labels: &[&str],
+    required_labels: &[String],
+) {
+    println!("Milestone: {}", report.milestone);
+
+    println!(
+        "\nOpen issues with blocking labels {:?}: {}",
+        blocking_labels,
+        report.blocking_issues.len()
+    );
+    for issue in &report.blocking_issues {
+        println!("  - #{} {} ({})", issue.number, issue.title, issue.url);
+    }
+
+    if !required_labels.is_empty() {
+        println!(
+            "\nClosed issues without required labels {:?}: {}",
+            required_labels,
+            report.unverified_issues.len()
+        );
+        for issue in &report.unverified_issues {
+            println!("  - #{} {} ({})", issue.number, issue.title, issue.url);
+        }
+    }
+
+    println!(
+        "\nResult: {}",
+        if report.ready { "READY" } else { "NOT READY" }
+    );
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+
+use crate::cmd::CommandTrait;
+use crate::component::repo::milestone::{MilestoneComponentTrait, MilestoneUpdateOptions};
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::result::CmdResult;
+
+pub const CMD_CLOSE_MILESTONE: &str = "close";
+
+pub struct CloseMilestoneCommand;
+
+impl CloseMilestoneCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for CloseMilestoneCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_CLOSE_MILESTONE)
+            .about("Close a milestone")
+            .args([Arg::new("milestone")
+                .value_name("milestone")
+                .help("Milestone")
+                .required(true)
+                .takes_value(true)])
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        let config = NoteConfig::new(matches);
+        let repo_component = RepoComponent::new(None, Arc::new(config));
+
+        let milestone = repo_component
+            .find_milestone(matches.value_of("milestone").unwrap())
+            .await?;
+
+        if matches.is_present("dry-run") {
+            println!("Dry run: the milestone {} would be closed", milestone.title);
+            return Ok(());
+        }
+
+        progress!(
+            format!("Closing the milestone {}", milestone.title),
+            repo_component.update_milestone(milestone.number, &MilestoneUpdateOptions {
+                state: Some("closed".to_string()),
+                ..Default::default()
+            }).await?;
+        );
+
+        println!("Successfully closed the milestone {}", milestone.title);
+        Ok(())
+    }
+}
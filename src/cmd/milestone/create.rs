@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+use hubcaps_ex::milestone::MilestoneOptions;
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+
+use crate::cmd::milestone::parse_due_date;
+use crate::cmd::CommandTrait;
+use crate::component::repo::milestone::MilestoneComponentTrait;
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::result::CmdResult;
+
+pub const CMD_CREATE_MILESTONE: &str = "create";
+
+pub struct CreateMilestoneCommand;
+
+impl CreateMilestoneCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for CreateMilestoneCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_CREATE_MILESTONE)
+            .about("Create a milestone")
+            .visible_alias("c")
+            .args([
+                Arg::new("milestone")
+                    .value_name("milestone")
+                    .help("Milestone")
+                    .required(true)
+                    .takes_value(true),
+                Arg::new("description")
+                    .value_name("string")
+                    .help("Milestone description")
+                    .long("description")
+                    .takes_value(true),
+                Arg::new("due-on")
+                    .value_name("date")
+                    .help("Milestone due date, e.g. 2021-12-31")
+                    .long("due-on")
+                    .takes_value(true),
+            ])
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        let config = NoteConfig::new(matches);
+        let repo_component = RepoComponent::new(None, Arc::new(config));
+
+        let options = MilestoneOptions {
+            title: matches.value_of("milestone").unwrap().to_string(),
+            state: "open".to_string(),
+            description: matches.value_of("description").map(String::from),
+            due_on: matches.value_of("due-on").map(parse_due_date).transpose()?,
+        };
+
+        if matches.is_present("dry-run") {
+            println!("Dry run: the milestone {} would be created", options.title);
+            return Ok(());
+        }
+
+        let milestone = progress!(
+            format!("Creating the milestone {}", options.title),
+            repo_component.create_milestone(&options).await?
+        );
+
+        println!(
+            "Successfully created the milestone {} ({})",
+            milestone.title, milestone.number
+        );
+        Ok(())
+    }
+}
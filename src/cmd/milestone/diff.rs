@@ -0,0 +1,269 @@
+use std::io::stdout;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate};
+use clap::{Arg, ArgMatches, Command};
+use hubcaps_ex::issues::Issue;
+use libcli_rs::output::{OutputFactory, OutputTrait};
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+use serde::{Deserialize, Serialize};
+
+use crate::cmd::CommandTrait;
+use crate::component::repo::issue::{IssueComponentTrait, IssueEvent};
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::result::{CmdResult, Result};
+use crate::util::get_output_format_from_args;
+
+pub const CMD_DIFF_MILESTONE: &str = "diff";
+
+const EVENT_MILESTONED: &str = "milestoned";
+const EVENT_DEMILESTONED: &str = "demilestoned";
+
+pub struct DiffMilestoneCommand;
+
+#[derive(Serialize, Deserialize)]
+struct MilestoneChange {
+    number: u64,
+    title: String,
+    url: String,
+    change: String,
+    from: String,
+    to: String,
+    date: String,
+    actor: String,
+}
+
+#[derive(Default)]
+struct IssueMilestoneHistory<'a> {
+    milestoned: Vec<&'a IssueEvent>,
+    demilestoned: Vec<&'a IssueEvent>,
+}
+
+impl DiffMilestoneCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for DiffMilestoneCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_DIFF_MILESTONE)
+            .about("Show issues moved between milestones, added to or removed from a milestone")
+            .visible_alias("d")
+            .args([
+                Arg::new("milestone")
+                    .value_name("milestone")
+                    .help("Milestone")
+                    .required(true)
+                    .takes_value(true),
+                Arg::new("next-milestone")
+                    .value_name("next-milestone")
+                    .help("Milestone to compare, issues moved from the milestone to it are reported as slipped")
+                    .takes_value(true),
+                Arg::new("since")
+                    .value_name("date")
+                    .help("Only report the changes since the date, e.g. the freeze date 2021-12-31")
+                    .long("since")
+                    .takes_value(true),
+                Arg::new("markdown")
+                    .help("Output as a Markdown table")
+                    .long("markdown"),
+            ])
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        let milestone = matches.value_of("milestone").unwrap();
+        let next_milestone = matches.value_of("next-milestone");
+        let since = matches
+            .value_of("since")
+            .map(|it| {
+                NaiveDate::parse_from_str(it, "%Y-%m-%d")
+                    .map_err(|err| anyhow!("invalid date {}: {}", it, err))
+            })
+            .transpose()?;
+
+        let config = NoteConfig::new(matches);
+        let repo_component = RepoComponent::new(None, Arc::new(config));
+
+        let mut milestones = vec![milestone];
+        milestones.extend(next_milestone);
+
+        // the milestone changes update the issues, so only the issues updated since the
+        // earliest milestone was created are checked instead of the events of the whole repo
+        let updated_since = match since {
+            Some(since) => since,
+            None => {
+                let mut dates = vec![];
+                for title in &milestones {
+                    let created_at = repo_component.get_milestone(title).await?.created_at;
+                    dates.push(
+                        DateTime::parse_from_rfc3339(&created_at)?
+                            .naive_utc()
+                            .date(),
+                    );
+                }
+                dates.into_iter().min().unwrap()
+            }
+        };
+
+        let issues = progress!(
+            "Searching issues updated since the milestones",
+            repo_component
+                .search_all_issues_by_query(&format!("updated:>={}", updated_since))
+                .await?;
+        );
+
+        let mut changes = vec![];
+        for issue in &issues {
+            let events = progress!(
+                format!("Listing events of issue {}", issue.number),
+                repo_component.list_events_of_issue(issue.number).await?;
+            );
+
+            let history = create_milestone_history(&events, &milestones, since)?;
+            changes.extend(diff_issue(issue, &history, milestone, next_milestone));
+        }
+        changes.sort_by(|a, b| a.date.cmp(&b.date).then(a.number.cmp(&b.number)));
+
+        if changes.is_empty() {
+            progress!("Milestone changes not found", ());
+            return Ok(());
+        }
+
+        if matches.is_present("markdown") {
+            print_markdown(&changes);
+            return Ok(());
+        }
+
+        let output_format = get_output_format_from_args(matches)?;
+        output!(output_format, .display(
+            stdout(),
+            &changes,
+            Some(vec!["number", "change", "from", "to", "date", "title"]),
+            None,
+        ))
+    }
+}
+
+fn create_milestone_history<'a>(
+    events: &'a [IssueEvent],
+    milestones: &[&str],
+    since: Option<NaiveDate>,
+) -> Result<IssueMilestoneHistory<'a>> {
+    let mut history = IssueMilestoneHistory::default();
+
+    for event in events {
+        let event_milestone = match &event.milestone {
+            Some(milestone) => milestone,
+            None => continue,
+        };
+        if !milestones.contains(&event_milestone.title.as_str()) {
+            continue;
+        }
+        if let Some(since) = since {
+            if DateTime::parse_from_rfc3339(&event.created_at)?
+                .naive_utc()
+                .date()
+                < since
+            {
+                continue;
+            }
+        }
+
+        match event.event.as_str() {
+            EVENT_MILESTONED => history.milestoned.push(event),
+            EVENT_DEMILESTONED => history.demilestoned.push(event),
+            _ => {}
+        }
+    }
+
+    history
+        .milestoned
+        .sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    history
+        .demilestoned
+        .sort_by(|a, b| a.created_at.cmp(&b.created_at));
+
+    Ok(history)
+}
+
+fn diff_issue(
+    issue: &Issue,
+    history: &IssueMilestoneHistory,
+    milestone: &str,
+    next_milestone: Option<&str>,
+) -> Option<MilestoneChange> {
+    let current_milestone = issue
+        .milestone
+        .as_ref()
+        .map(|it| it.title.as_str())
+        .unwrap_or_default();
+
+    let change = |change: &str, from: &str, to: &str, event: &IssueEvent| MilestoneChange {
+        number: issue.number,
+        title: issue.title.clone(),
+        url: issue.html_url.clone(),
+        change: change.to_string(),
+        from: from.to_string(),
+        to: to.to_string(),
+        date: event.created_at.clone(),
+        actor: event
+            .actor
+            .as_ref()
+            .map(|it| it.login.clone())
+            .unwrap_or_default(),
+    };
+
+    if let Some(next_milestone) = next_milestone {
+        if current_milestone == next_milestone
+            && last_event(&history.demilestoned, milestone).is_some()
+        {
+            if let Some(event) = last_event(&history.milestoned, next_milestone) {
+                return Some(change("slipped", milestone, next_milestone, event));
+            }
+        }
+    }
+
+    for title in [Some(milestone), next_milestone].into_iter().flatten() {
+        if current_milestone == title {
+            if let Some(event) = last_event(&history.milestoned, title) {
+                return Some(change("added", "", title, event));
+            }
+        } else if let Some(event) = last_event(&history.demilestoned, title) {
+            return Some(change("removed", title, current_milestone, event));
+        }
+    }
+
+    None
+}
+
+fn last_event<'a>(events: &[&'a IssueEvent], milestone: &str) -> Option<&'a IssueEvent> {
+    events
+        .iter()
+        .rev()
+        .find(|it| it.milestone.as_ref().map(|m| m.title.as_str()) == Some(milestone))
+        .copied()
+}
+
+fn print_markdown(changes: &[MilestoneChange]) {
+    println!("| Issue | Change | From | To | Date | Actor |");
+    println!("|---|---|---|---|---|---|");
+
+    for change in changes {
+        println!(
+            "| [#{}]({}) {} | {} | {} | {} | {} | {} |",
+            change.number,
+            change.url,
+            change.title.replace('|', "\\|"),
+            change.change,
+            change.from,
+            change.to,
+            &change.date[..change.date.len().min(10)],
+            change.actor
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use std::io::stdout;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+use libcli_rs::output::{OutputFactory, OutputTrait};
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+
+use crate::cmd::CommandTrait;
+use crate::component::repo::milestone::MilestoneComponentTrait;
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::result::CmdResult;
+use crate::util::get_output_format_from_args;
+
+pub const CMD_LIST_MILESTONE: &str = "list";
+
+pub struct ListMilestoneCommand;
+
+impl ListMilestoneCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for ListMilestoneCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_LIST_MILESTONE)
+            .about("List milestones")
+            .visible_alias("l")
+            .args([Arg::new("state")
+                .value_name("string")
+                .help("Milestone state")
+                .long("state")
+                .takes_value(true)
+                .default_value("open")
+                .possible_values(["open", "closed", "all"])])
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        let config = NoteConfig::new(matches);
+        let repo_component = RepoComponent::new(None, Arc::new(config));
+
+        let milestones = progress!(
+            "Listing milestones",
+            repo_component.list_milestones(matches.value_of("state").unwrap()).await?;
+        );
+
+        if milestones.is_empty() {
+            progress!("Milestones not found", ());
+            return Ok(());
+        }
+
+        let output_format = get_output_format_from_args(matches)?;
+        output!(output_format, .display(
+            stdout(),
+            &milestones,
+            Some(vec!["number", "title", "state", "due_on", "open_issues", "closed_issues"]),
+            None,
+        ))
+    }
+}
@@ -0,0 +1,65 @@
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+pub use check::{CheckMilestoneCommand, CMD_CHECK_MILESTONE};
+pub use close::{CloseMilestoneCommand, CMD_CLOSE_MILESTONE};
+pub use create::{CreateMilestoneCommand, CMD_CREATE_MILESTONE};
+pub use diff::{DiffMilestoneCommand, CMD_DIFF_MILESTONE};
+pub use list::{ListMilestoneCommand, CMD_LIST_MILESTONE};
+pub use rename::{RenameMilestoneCommand, CMD_RENAME_MILESTONE};
+pub use report::{ReportMilestoneCommand, CMD_REPORT_MILESTONE};
+pub use rollover::{RolloverMilestoneCommand, CMD_ROLLOVER_MILESTONE};
+
+use crate::cmd::{create_cmd, CommandSetting, CommandTrait};
+use crate::result::Result;
+
+mod check;
+mod close;
+mod create;
+mod diff;
+mod list;
+mod rename;
+mod report;
+mod rollover;
+
+pub const CMD_MILESTONE: &str = "milestone";
+
+pub struct MilestoneCommand {
+    setting: CommandSetting,
+}
+
+impl MilestoneCommand {
+    pub fn new() -> Self {
+        MilestoneCommand {
+            setting: CommandSetting {
+                name: CMD_MILESTONE,
+                about: "Milestone commands",
+                commands: hashmap! {
+                    CMD_LIST_MILESTONE => create_cmd(Box::new(ListMilestoneCommand::new())),
+                    CMD_CREATE_MILESTONE => create_cmd(Box::new(CreateMilestoneCommand::new())),
+                    CMD_CLOSE_MILESTONE => create_cmd(Box::new(CloseMilestoneCommand::new())),
+                    CMD_RENAME_MILESTONE => create_cmd(Box::new(RenameMilestoneCommand::new())),
+                    CMD_ROLLOVER_MILESTONE => create_cmd(Box::new(RolloverMilestoneCommand::new())),
+                    CMD_REPORT_MILESTONE => create_cmd(Box::new(ReportMilestoneCommand::new())),
+                    CMD_DIFF_MILESTONE => create_cmd(Box::new(DiffMilestoneCommand::new())),
+                    CMD_CHECK_MILESTONE => create_cmd(Box::new(CheckMilestoneCommand::new())),
+                },
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl CommandTrait for MilestoneCommand {
+    fn setting(&self) -> &CommandSetting {
+        &self.setting
+    }
+}
+
+fn parse_due_date(date: &str) -> Result<String> {
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|err| anyhow!("invalid due date {}: {}", date, err))?;
+
+    Ok(format!("{}T00:00:00Z", date))
+}
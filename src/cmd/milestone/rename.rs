@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+
+use crate::cmd::CommandTrait;
+use crate::component::repo::milestone::{MilestoneComponentTrait, MilestoneUpdateOptions};
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::result::CmdResult;
+
+pub const CMD_RENAME_MILESTONE: &str = "rename";
+
+pub struct RenameMilestoneCommand;
+
+impl RenameMilestoneCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for RenameMilestoneCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_RENAME_MILESTONE)
+            .about("Rename a milestone")
+            .args([
+                Arg::new("milestone")
+                    .value_name("milestone")
+                    .help("Milestone")
+                    .required(true)
+                    .takes_value(true),
+                Arg::new("new-milestone")
+                    .value_name("new-milestone")
+                    .help("New milestone title")
+                    .required(true)
+                    .takes_value(true),
+            ])
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        let config = NoteConfig::new(matches);
+        let repo_component = RepoComponent::new(None, Arc::new(config));
+        let new_title = matches.value_of("new-milestone").unwrap();
+
+        let milestone = repo_component
+            .find_milestone(matches.value_of("milestone").unwrap())
+            .await?;
+
+        if matches.is_present("dry-run") {
+            println!(
+                "Dry run: the milestone {} would be renamed to {}",
+                milestone.title, new_title
+            );
+            return Ok(());
+        }
+
+        progress!(
+            format!("Renaming the milestone {} to {}", milestone.title, new_title),
+            repo_component.update_milestone(milestone.number, &MilestoneUpdateOptions {
+                title: Some(new_title.to_string()),
+                ..Default::default()
+            }).await?;
+        );
+
+        println!(
+            "Successfully renamed the milestone {} to {}",
+            milestone.title, new_title
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,333 @@
+use std::fmt::Write;
+use std::fs;
+use std::io::stdout;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use clap::{Arg, ArgMatches, Command};
+use hubcaps_ex::issues::Issue;
+use libcli_rs::output::{OutputFactory, OutputFormat, OutputTrait};
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+use serde::{Deserialize, Serialize};
+
+use crate::cmd::CommandTrait;
+use crate::component::repo::issue::IssueComponentTrait;
+use crate::component::repo::milestone::MilestoneComponentTrait;
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::result::{CmdResult, Result};
+use crate::util::get_output_format_from_args;
+
+pub const CMD_REPORT_MILESTONE: &str = "report";
+
+const MISC_SECTION: &str = "misc";
+const SVG_WIDTH: f64 = 800.0;
+const SVG_HEIGHT: f64 = 400.0;
+const SVG_PADDING: f64 = 40.0;
+
+pub struct ReportMilestoneCommand;
+
+#[derive(Serialize, Deserialize)]
+struct MilestoneReport {
+    milestone: String,
+    state: String,
+    open_issues: usize,
+    closed_issues: usize,
+    percent_complete: f64,
+    due_on: Option<String>,
+    days_to_due: Option<i64>,
+    sections: Vec<SectionReport>,
+    burndown: Vec<BurndownPoint>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SectionReport {
+    label: String,
+    open_issues: usize,
+    closed_issues: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BurndownPoint {
+    date: String,
+    open_issues: usize,
+}
+
+impl ReportMilestoneCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for ReportMilestoneCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_REPORT_MILESTONE)
+            .about("Report the progress and burndown of a milestone")
+            .args([
+                Arg::new("milestone")
+                    .value_name("milestone")
+                    .help("Milestone")
+                    .required(true)
+                    .takes_value(true),
+                Arg::new("labels")
+                    .help("Labels to report as sections, the other issues are reported as misc")
+                    .long("labels")
+                    .value_delimiter(' ')
+                    .takes_value(true),
+                Arg::new("svg")
+                    .value_name("file")
+                    .help("Write the burndown chart to a SVG file")
+                    .long("svg")
+                    .takes_value(true),
+            ])
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        let title = matches.value_of("milestone").unwrap();
+        let labels: Vec<_> = matches
+            .values_of("labels")
+            .map(|it| it.collect())
+            .unwrap_or_default();
+
+        let mut config = NoteConfig::new(matches);
+        config.milestone = Some(title.to_string());
+        let repo_component = RepoComponent::new(None, Arc::new(config));
+
+        let milestone = repo_component.find_milestone(title).await?;
+        let issues = progress!(
+            format!("Searching issues of the milestone {}", title),
+            repo_component.search_issues_by_query("is:issue").await?;
+        );
+
+        let today = Utc::now().naive_utc().date();
+        let open_issues = issues.iter().filter(|it| it.state == "open").count();
+        let closed_issues = issues.len() - open_issues;
+        let due_date = milestone.due_on.as_deref().map(parse_date).transpose()?;
+
+        let report = MilestoneReport {
+            milestone: milestone.title.clone(),
+            state: milestone.state.clone(),
+            open_issues,
+            closed_issues,
+            percent_complete: if issues.is_empty() {
+                0.0
+            } else {
+                (closed_issues as f64 * 10000.0 / issues.len() as f64).round() / 100.0
+            },
+            due_on: due_date.map(|it| it.to_string()),
+            days_to_due: due_date.map(|it| (it - today).num_days()),
+            sections: create_section_reports(&issues, &labels),
+            burndown: create_burndown(&issues, parse_date(&milestone.created_at)?, today)?,
+        };
+
+        if let Some(path) = matches.value_of("svg") {
+            fs::write(path, render_burndown_svg(&report))?;
+        }
+
+        let output_format = get_output_format_from_args(matches)?;
+        if let OutputFormat::Console = output_format {
+            print_report(&report);
+            return output!(output_format, .display(
+                stdout(),
+                &report.sections,
+                Some(vec!["label", "open_issues", "closed_issues"]),
+                None,
+            ));
+        }
+
+        output!(output_format, .display(stdout(), &report, None, None))
+    }
+}
+
+fn parse_date(date: &str) -> Result<NaiveDate> {
+    Ok(DateTime::parse_from_rfc3339(date)?.naive_utc().date())
+}
+
+fn create_section_reports(issues: &[Issue], labels: &[&str]) -> Vec<SectionReport> {
+    let mut sections: Vec<_> = labels
+        .iter()
+        .chain([&MISC_SECTION])
+        .map(|it| SectionReport {
+            label: it.to_string(),
+            open_issues: 0,
+            closed_issues: 0,
+        })
+        .collect();
+
+    for issue in issues {
+        let index = labels
+            .iter()
+            .position(|l| issue.labels.iter().any(|il| il.name == *l))
+            .unwrap_or(labels.len());
+
+        if issue.state == "open" {
+            sections[index].open_issues += 1;
+        } else {
+            sections[index].closed_issues += 1;
+        }
+    }
+
+    sections
+}
+
+fn create_burndown(
+    issues: &[Issue],
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<Vec<BurndownPoint>> {
+    let mut closed_dates = vec![];
+    for issue in issues {
+        if let Some(closed_at) = &issue.closed_at {
+            if issue.state == "closed" {
+                closed_dates.push(parse_date(closed_at)?);
+            }
+        }
+    }
+
+    let mut burndown = vec![];
+    let mut date = start;
+    while date <= end {
+        let closed_issues = closed_dates.iter().filter(|it| **it <= date).count();
+        burndown.push(BurndownPoint {
+            date: date.to_string(),
+            open_issues: issues.len() - closed_issues,
+        });
+        date += Duration::days(1);
+    }
+
+    Ok(burndown)
+}
+
+fn print_report(report: &MilestoneReport) {
+    println!("Milestone: {} ({})", report.milestone, report.state);
+    println!(
+        "Progress: {}% complete, {} open, {} closed",
+        report.percent_complete, report.open_issues, report.closed_issues
+    );
+    match (&report.due_on, report.days_to_due) {
+        (Some(due_on), Some(days)) => println!("Due on: {} ({} days left)", due_on, days),
+        _ => println!("Due on: N/A"),
+    }
+
+    println!("\nBurndown:");
+    let total = report.open_issues + report.closed_issues;
+    for point in &report.burndown {
+        let width = (point.open_issues * 50)
+            .checked_div(total)
+            .unwrap_or_default();
+        println!(
+            "  {} {:>4} {}",
+            point.date,
+            point.open_issues,
+            "#".repeat(width)
+        );
+    }
+    println!();
+}
+
+fn render_burndown_svg(report: &MilestoneReport) -> String {
+    let points = &report.burndown;
+    let max_open = points
+        .iter()
+        .map(|it| it.open_issues)
+        .max()
+        .unwrap_or(0)
+        .max(1) as f64;
+    let step = if points.len() > 1 {
+        (SVG_WIDTH - SVG_PADDING * 2.0) / (points.len() - 1) as f64
+    } else {
+        0.0
+    };
+    let to_y = |open_issues: usize| {
+        SVG_HEIGHT - SVG_PADDING - open_issues as f64 / max_open * (SVG_HEIGHT - SVG_PADDING * 2.0)
+    };
+
+    let mut polyline = String::new();
+    for (index, point) in points.iter().enumerate() {
+        write!(
+            polyline,
+            "{:.1},{:.1} ",
+            SVG_PADDING + step * index as f64,
+            to_y(point.open_issues)
+        )
+        .unwrap();
+    }
+    polyline.pop();
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = SVG_WIDTH,
+        h = SVG_HEIGHT
+    )
+    .unwrap();
+    writeln!(svg, r#"  <rect width="100%" height="100%" fill="white"/>"#).unwrap();
+    writeln!(
+        svg,
+        r#"  <text x="{}" y="{}" font-family="sans-serif" font-size="16">{} burndown ({}% complete)</text>"#,
+        SVG_PADDING,
+        SVG_PADDING / 2.0 + 5.0,
+        escape_xml(&report.milestone),
+        report.percent_complete
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r#"  <line x1="{p}" y1="{b}" x2="{r}" y2="{b}" stroke="black"/>"#,
+        p = SVG_PADDING,
+        b = SVG_HEIGHT - SVG_PADDING,
+        r = SVG_WIDTH - SVG_PADDING
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r#"  <line x1="{p}" y1="{p}" x2="{p}" y2="{b}" stroke="black"/>"#,
+        p = SVG_PADDING,
+        b = SVG_HEIGHT - SVG_PADDING
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r#"  <text x="5" y="{}" font-family="sans-serif" font-size="12">{}</text>"#,
+        SVG_PADDING, max_open
+    )
+    .unwrap();
+
+    if let (Some(first), Some(last)) = (points.first(), points.last()) {
+        writeln!(
+            svg,
+            r#"  <text x="{}" y="{}" font-family="sans-serif" font-size="12">{}</text>"#,
+            SVG_PADDING,
+            SVG_HEIGHT - SVG_PADDING / 2.0,
+            first.date
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            r#"  <text x="{}" y="{}" font-family="sans-serif" font-size="12" text-anchor="end">{}</text>"#,
+            SVG_WIDTH - SVG_PADDING,
+            SVG_HEIGHT - SVG_PADDING / 2.0,
+            last.date
+        )
+        .unwrap();
+    }
+
+    writeln!(
+        svg,
+        r#"  <polyline points="{}" fill="none" stroke="steelblue" stroke-width="2"/>"#,
+        polyline
+    )
+    .unwrap();
+    svg.push_str("</svg>\n");
+
+    svg
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+
+use crate::checkpoint::PostStep;
+use crate::cmd::issue::create_issues_info_to_update_milestone;
+use crate::cmd::{update_issues, CommandTrait, IssueUpdateOptions};
+use crate::component::repo::issue::IssueComponentTrait;
+use crate::component::repo::milestone::MilestoneComponentTrait;
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::result::CmdResult;
+
+pub const CMD_ROLLOVER_MILESTONE: &str = "rollover";
+
+pub struct RolloverMilestoneCommand;
+
+impl RolloverMilestoneCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for RolloverMilestoneCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_ROLLOVER_MILESTONE)
+            .about("Move open issues to the next milestone, then close the original milestone")
+            .visible_alias("r")
+            .args([
+                Arg::new("milestone")
+                    .value_name("milestone")
+                    .help("Milestone to close")
+                    .required(true)
+                    .takes_value(true),
+                Arg::new("next-milestone")
+                    .value_name("next-milestone")
+                    .help("Milestone to move open issues to")
+                    .required(true)
+                    .takes_value(true),
+                Arg::new("comment")
+                    .value_name("string")
+                    .help("Comment on the moved issues")
+                    .long("comment")
+                    .takes_value(true),
+            ])
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        let mut config = NoteConfig::new(matches);
+        config.milestone = matches.value_of("milestone").map(String::from);
+        config.state = "open".to_string();
+        let repo_component = RepoComponent::new(None, Arc::new(config));
+
+        let milestone = repo_component
+            .find_milestone(matches.value_of("milestone").unwrap())
+            .await?;
+        let next_milestone = repo_component
+            .find_milestone(matches.value_of("next-milestone").unwrap())
+            .await?;
+
+        let issues = progress!(
+            format!("Searching open issues of the milestone {}", milestone.title),
+            repo_component.search_issues_by_query("").await?;
+        );
+        let mut issues_to_update =
+            create_issues_info_to_update_milestone(&issues, Some(&next_milestone));
+        if let Some(comment) = matches.value_of("comment") {
+            for issue in &mut issues_to_update {
+                issue.comment = Some(comment.to_string());
+            }
+        }
+
+        update_issues(
+            matches,
+            &repo_component,
+            issues_to_update,
+            &format!("to the milestone {}", next_milestone.title),
+            IssueUpdateOptions {
+                post_step: Some(PostStep::CloseMilestone {
+                    number: milestone.number,
+                    title: milestone.title.clone(),
+                }),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+}
@@ -1,20 +1,60 @@
 use std::collections::HashMap;
 use std::env::args;
+use std::io::{stdin, stdout, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use anyhow::anyhow;
 use async_trait::async_trait;
 use clap::{ArgMatches, Command};
+use futures::{future, stream, StreamExt};
+use libcli_rs::output::{OutputFactory, OutputFormat, OutputTrait};
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+use log::warn;
+use serde::{Deserialize, Serialize};
 
+use crate::checkpoint::{load_checkpoint, Checkpoint, PostStep};
+use crate::component::repo::issue::{
+    IssueComponentTrait, IssueMilestone, IssueUpdate, IssueUpdateResult, IssueUpdateStatus,
+};
+use crate::component::repo::label::LabelComponentTrait;
+use crate::component::repo::milestone::{MilestoneComponentTrait, MilestoneUpdateOptions};
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::journal::{load_journal_entry, save_journal_entry, JournalEntry};
 use crate::result::{CmdResult, Result};
-use crate::util::init_log;
+use crate::util::{get_output_format_from_args, init_client_setting, init_log};
 
 mod arg;
 pub mod issue;
+pub mod label;
+pub mod milestone;
 pub mod note;
+pub mod rules;
+pub mod serve;
+pub mod undo;
 
 pub type CmdBox = Box<dyn CommandTrait + Send + Sync>;
 pub type CmdGroup = HashMap<&'static str, CmdBox>;
 
+/// The options of bulk issue updates besides the issues.
+#[derive(Default)]
+pub struct IssueUpdateOptions {
+    /// The ID of the journal entry reverted by the updates, if it's an undo.
+    pub undo_of: Option<String>,
+    pub post_step: Option<PostStep>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct IssueUpdateSummary {
+    succeeded: usize,
+    failed: usize,
+    skipped: usize,
+    journal: Option<String>,
+    checkpoint: Option<String>,
+    issues: Vec<IssueUpdateResult>,
+}
+
 pub struct CommandSetting {
     name: &'static str,
     about: &'static str,
@@ -45,9 +85,7 @@ pub trait CommandTrait {
     }
 
     async fn process(&self, matches: &ArgMatches) -> CmdResult {
-        if let Some(log_level) = matches.value_of("log-level") {
-            init_log(log_level)?;
-        }
+        init_process(matches)?;
 
         if let Some((cmd, sub_matches)) = matches.subcommand() {
             let cmd = self.setting().commands.get(cmd).unwrap();
@@ -62,6 +100,95 @@ pub trait CommandTrait {
 
         Ok(self.app().print_help()?)
     }
+
+    /// Updates the changed issues, or only shows the changes if `--dry-run` is specified.
+    ///
+    /// The update is refused if exceeding `--max-issues` without `--force`, and needs to be
+    /// confirmed on a TTY without `--yes`. Issues are updated concurrently, and the failed ones
+    /// don't stop the others but fail the command after reporting the results. The succeeded
+    /// updates are recorded in a journal entry to be able to undo.
+    ///
+    /// The issues to update and the progress are saved in a checkpoint before updating, to be
+    /// able to resume by `--resume` if interrupted or failed.
+    async fn process_issue_updates(
+        &self,
+        matches: &ArgMatches,
+        repo_component: &(dyn IssueComponentTrait + Send + Sync),
+        issues: Vec<IssueUpdate>,
+        action: &str,
+    ) -> CmdResult {
+        update_issues(
+            matches,
+            repo_component,
+            issues,
+            action,
+            IssueUpdateOptions::default(),
+        )
+        .await
+    }
+}
+
+/// Updates the issues like [`CommandTrait::process_issue_updates`], with the options recorded in
+/// the checkpoint.
+pub async fn update_issues(
+    matches: &ArgMatches,
+    repo_component: &(dyn IssueComponentTrait + Send + Sync),
+    issues: Vec<IssueUpdate>,
+    action: &str,
+    options: IssueUpdateOptions,
+) -> CmdResult {
+    let (changed_issues, unchanged_issues): (Vec<_>, Vec<_>) =
+        issues.into_iter().partition(|it| it.is_changed());
+
+    if matches.is_present("dry-run") {
+        return print_issue_updates(matches, &changed_issues);
+    }
+
+    if !changed_issues.is_empty() {
+        confirm_issue_updates(matches, changed_issues.len(), action)?;
+    }
+
+    let skipped_results = unchanged_issues
+        .iter()
+        .map(|it| {
+            IssueUpdateResult::new(
+                it,
+                IssueUpdateStatus::Skipped,
+                Some("no change".to_string()),
+            )
+        })
+        .collect();
+
+    let (owner, repo) = repo_component.repo_name();
+    let mut checkpoint = Checkpoint::new(owner, repo, action, changed_issues);
+    checkpoint.undo_of = options.undo_of;
+    checkpoint.post_step = options.post_step;
+
+    execute_checkpoint(matches, repo_component, checkpoint, skipped_results).await
+}
+
+/// Resumes the issue updates of the checkpoint which are not completed, without the args of the
+/// command creating the checkpoint.
+pub async fn resume_issue_updates(matches: &ArgMatches, checkpoint: &str) -> CmdResult {
+    init_process(matches)?;
+    if !matches.is_present("token") {
+        return Err(anyhow!("GitHub token is mandatory"));
+    }
+
+    let checkpoint = load_checkpoint(checkpoint)?;
+    let remaining_issues = checkpoint.remaining_issues();
+
+    if matches.is_present("dry-run") {
+        return print_issue_updates(matches, &remaining_issues);
+    }
+
+    if !remaining_issues.is_empty() {
+        confirm_issue_updates(matches, remaining_issues.len(), &checkpoint.action)?;
+    }
+
+    let repo_component = create_checkpoint_repo_component(matches, &checkpoint);
+
+    execute_checkpoint(matches, &repo_component, checkpoint, vec![]).await
 }
 
 pub fn get_app_matches(app: Command) -> ArgMatches {
@@ -79,6 +206,14 @@ pub fn create_cmd(c: CmdBox) -> CmdBox {
     c
 }
 
+fn init_process(matches: &ArgMatches) -> CmdResult {
+    if let Some(log_level) = matches.value_of("log-level") {
+        init_log(log_level)?;
+    }
+
+    init_client_setting(matches)
+}
+
 fn check_github_args(matches: &ArgMatches) -> Result<()> {
     if matches.is_present("owner") && matches.is_present("repo") && matches.is_present("token") {
         return Ok(());
@@ -86,3 +221,319 @@ fn check_github_args(matches: &ArgMatches) -> Result<()> {
 
     Err(anyhow!("GitHub owner, repo, and token are mandatory"))
 }
+
+fn confirm_issue_updates(matches: &ArgMatches, count: usize, action: &str) -> CmdResult {
+    let max_issues: usize = matches.value_of_t("max-issues")?;
+    if count > max_issues && !matches.is_present("force") {
+        return Err(anyhow!(
+            "{} issues matched, more than the maximum {} issues, use --max-issues or --force to override",
+            count,
+            max_issues
+        ));
+    }
+
+    confirm(
+        matches,
+        &format!("{} issues will be updated {}", count, action),
+    )
+}
+
+/// Asks for the confirmation of the changes unless `--yes` is specified.
+fn confirm(matches: &ArgMatches, message: &str) -> CmdResult {
+    if matches.is_present("yes") {
+        return Ok(());
+    }
+
+    if !atty::is(atty::Stream::Stdin) {
+        return Err(anyhow!(
+            "{} without confirmation, use --yes to confirm",
+            message
+        ));
+    }
+
+    print!("{}. Continue? [y/N] ", message);
+    stdout().flush()?;
+
+    let mut answer = String::new();
+    stdin().read_line(&mut answer)?;
+    if !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+        return Err(anyhow!("update cancelled"));
+    }
+
+    Ok(())
+}
+
+fn print_issue_updates(matches: &ArgMatches, issues: &[IssueUpdate]) -> CmdResult {
+    let output_format = get_output_format_from_args(matches)?;
+    if !matches!(output_format, OutputFormat::Console) {
+        return output!(output_format, .display(stdout(), &issues.to_vec(), None, None));
+    }
+
+    fn milestone_str(milestone: &Option<IssueMilestone>) -> &str {
+        milestone
+            .as_ref()
+            .map(|it| it.title.as_str())
+            .unwrap_or("N/A")
+    }
+
+    for issue in issues {
+        println!("#{} {}", issue.number, issue.title);
+        println!(
+            "  labels:    {:?} -> {:?}",
+            issue.before.labels, issue.after.labels
+        );
+        println!(
+            "  milestone: {} -> {}",
+            milestone_str(&issue.before.milestone),
+            milestone_str(&issue.after.milestone)
+        );
+        println!(
+            "  assignees: {:?} -> {:?}",
+            issue.before.assignees, issue.after.assignees
+        );
+        if issue.before.state != issue.after.state {
+            println!(
+                "  state:     {} -> {}{}",
+                issue.before.state,
+                issue.after.state,
+                issue
+                    .state_reason
+                    .as_ref()
+                    .map(|it| format!(" ({})", it))
+                    .unwrap_or_default()
+            );
+        }
+        if let Some(comment) = &issue.comment {
+            println!("  comment:   {}", comment);
+        }
+    }
+
+    println!("Dry run: {} issues would be updated", issues.len());
+    Ok(())
+}
+
+async fn execute_checkpoint(
+    matches: &ArgMatches,
+    repo_component: &(dyn IssueComponentTrait + Send + Sync),
+    mut checkpoint: Checkpoint,
+    mut results: Vec<IssueUpdateResult>,
+) -> CmdResult {
+    let concurrency: usize = matches.value_of_t("concurrency")?;
+    let issues = checkpoint.remaining_issues();
+    let total = issues.len();
+    checkpoint.save()?;
+
+    let interrupted = Arc::new(AtomicBool::new(false));
+    let signal_handler = {
+        let interrupted = interrupted.clone();
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                warn!("interrupted, waiting for the in-flight issue updates");
+                interrupted.store(true, Ordering::SeqCst);
+            }
+        })
+    };
+
+    // stop taking the remaining issues once interrupted, but finish the in-flight updates
+    let mut updates = stream::iter(issues)
+        .take_while(|_| future::ready(!interrupted.load(Ordering::SeqCst)))
+        .map(|issue| async move { repo_component.update_issue_with_result(&issue).await })
+        .buffer_unordered(concurrency.max(1));
+
+    let pb = ProgressBar::new();
+    let mut updated = 0;
+    while let Some(result) = updates.next().await {
+        updated += 1;
+        pb.update(&format!(
+            "Updating issues {} ({}/{})",
+            checkpoint.action, updated, total
+        ))?;
+
+        if result.status != IssueUpdateStatus::Failed {
+            checkpoint.completed.push(result.number);
+            checkpoint.save()?;
+        }
+        results.push(result);
+    }
+    pb.done_without_indicator("")?;
+    signal_handler.abort();
+
+    // the post step is kept in the checkpoint to run on resume if it failed
+    let mut post_step_result = Ok(());
+    if checkpoint.is_completed() {
+        if let Some(post_step) = &checkpoint.post_step {
+            post_step_result = run_post_step(matches, &checkpoint, post_step).await;
+        }
+    }
+
+    let saved_checkpoint = if checkpoint.is_completed() && post_step_result.is_ok() {
+        checkpoint.remove()?;
+        None
+    } else {
+        Some(checkpoint.id.clone())
+    };
+
+    let journal = save_issue_updates_journal(repo_component, &checkpoint, &results)?;
+    print_issue_update_results(
+        matches,
+        results,
+        journal,
+        saved_checkpoint,
+        &checkpoint.action,
+    )?;
+
+    if interrupted.load(Ordering::SeqCst) {
+        return Err(anyhow!("interrupted"));
+    }
+
+    post_step_result
+}
+
+async fn run_post_step(
+    matches: &ArgMatches,
+    checkpoint: &Checkpoint,
+    post_step: &PostStep,
+) -> CmdResult {
+    let repo_component = create_checkpoint_repo_component(matches, checkpoint);
+
+    match post_step {
+        PostStep::CloseMilestone { number, title } => {
+            progress!(
+                format!("Closing the milestone {}", title),
+                repo_component.update_milestone(*number, &MilestoneUpdateOptions {
+                    state: Some("closed".to_string()),
+                    ..Default::default()
+                }).await?;
+            );
+        }
+        PostStep::DeleteLabel { name } => {
+            // deleting the label strips it from all issues, so keep it if any issue is not moved yet
+            let remaining_issues = progress!(
+                format!("Listing issues of the label {}", name),
+                repo_component.list_all_issues(&[name.as_str()]).await?;
+            );
+            if !remaining_issues.is_empty() {
+                return Err(anyhow!(
+                    "the label {} is still used by {} issues, not deleted",
+                    name,
+                    remaining_issues.len()
+                ));
+            }
+
+            progress!(
+                format!("Deleting the label {}", name),
+                repo_component.delete_label(name).await?;
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn create_checkpoint_repo_component(
+    matches: &ArgMatches,
+    checkpoint: &Checkpoint,
+) -> impl IssueComponentTrait + LabelComponentTrait + MilestoneComponentTrait {
+    let config = NoteConfig {
+        owner: checkpoint.owner.clone(),
+        repo: checkpoint.repo.clone(),
+        token: matches.value_of("token").unwrap().to_string(),
+        ..Default::default()
+    };
+
+    RepoComponent::new(None, Arc::new(config))
+}
+
+/// Saves the succeeded updates in a journal entry, and marks the entry reverted by the
+/// checkpoint as undone once all the issues are updated.
+fn save_issue_updates_journal(
+    repo_component: &(dyn IssueComponentTrait + Send + Sync),
+    checkpoint: &Checkpoint,
+    results: &[IssueUpdateResult],
+) -> Result<Option<String>> {
+    if let (Some(undo_of), true) = (&checkpoint.undo_of, checkpoint.is_completed()) {
+        let mut entry = load_journal_entry(undo_of)?;
+        entry.undone = true;
+        save_journal_entry(&entry)?;
+    }
+
+    let succeeded_issues: Vec<_> = checkpoint
+        .issues
+        .iter()
+        .filter(|issue| {
+            results
+                .iter()
+                .any(|it| it.number == issue.number && it.status == IssueUpdateStatus::Succeeded)
+        })
+        .cloned()
+        .collect();
+    if succeeded_issues.is_empty() {
+        return Ok(None);
+    }
+
+    let (owner, repo) = repo_component.repo_name();
+    let entry = JournalEntry::new(owner, repo, succeeded_issues, checkpoint.undo_of.as_deref());
+    save_journal_entry(&entry)?;
+
+    Ok(Some(entry.id))
+}
+
+fn print_issue_update_results(
+    matches: &ArgMatches,
+    results: Vec<IssueUpdateResult>,
+    journal: Option<String>,
+    checkpoint: Option<String>,
+    action: &str,
+) -> CmdResult {
+    let count = |status: IssueUpdateStatus| results.iter().filter(|it| it.status == status).count();
+    let summary = IssueUpdateSummary {
+        succeeded: count(IssueUpdateStatus::Succeeded),
+        failed: count(IssueUpdateStatus::Failed),
+        skipped: count(IssueUpdateStatus::Skipped),
+        journal,
+        checkpoint,
+        issues: results,
+    };
+
+    let output_format = get_output_format_from_args(matches)?;
+    if matches!(output_format, OutputFormat::Console) {
+        for issue in summary
+            .issues
+            .iter()
+            .filter(|it| it.status != IssueUpdateStatus::Succeeded)
+        {
+            println!(
+                "{:?} #{} {}: {}",
+                issue.status,
+                issue.number,
+                issue.title,
+                issue.reason.as_deref().unwrap_or_default()
+            );
+        }
+
+        println!(
+            "Updated issues {}: {} succeeded, {} failed, {} skipped",
+            action, summary.succeeded, summary.failed, summary.skipped
+        );
+        if let Some(journal) = &summary.journal {
+            println!(
+                "Saved the journal {}, use `undo {}` to revert",
+                journal, journal
+            );
+        }
+        if let Some(checkpoint) = &summary.checkpoint {
+            println!(
+                "Saved the checkpoint {}, use `renote --resume {}` to continue the remaining issues",
+                checkpoint, checkpoint
+            );
+        }
+    } else {
+        output!(output_format, .display(stdout(), &summary, None, None))?;
+    }
+
+    if summary.failed > 0 {
+        return Err(anyhow!("failed to update {} issues", summary.failed));
+    }
+
+    Ok(())
+}
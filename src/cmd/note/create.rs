@@ -1,14 +1,12 @@
-use std::fs::File;
-use std::path::Path;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use clap::{Arg, ArgMatches, Command};
+use clap::{ArgMatches, Command};
 use libcli_rs::progress::{ProgressBar, ProgressTrait};
 
+use crate::cmd::note::{create_config_arg, load_note_config};
 use crate::cmd::CommandTrait;
 use crate::component::note::{NoteComponent, NoteComponentTrait};
-use crate::config::NoteConfig;
 use crate::result::CmdResult;
 
 pub const CMD_CREATE_NOTE: &str = "create";
@@ -27,11 +25,7 @@ impl CommandTrait for CreateNoteCommand {
         Command::new(CMD_CREATE_NOTE)
             .about("Create the release note")
             .visible_alias("c")
-            .args([Arg::new("config")
-                .help("Issue search config yaml file")
-                .long("config")
-                .required(true)
-                .takes_value(true)])
+            .args([create_config_arg()])
     }
 
     fn validate(&self, _matches: &ArgMatches) -> CmdResult {
@@ -39,20 +33,7 @@ impl CommandTrait for CreateNoteCommand {
     }
 
     async fn process(&self, matches: &ArgMatches) -> CmdResult {
-        let config_path = matches.value_of("config").unwrap();
-        let file = File::open(Path::new(config_path))
-            .expect(format!("expect {} found", config_path).as_str());
-        let mut note_config: NoteConfig =
-            serde_yaml::from_reader(file).expect("expect node config file");
-
-        // override by the global settings
-        if matches.is_present("owner") {
-            note_config.owner = matches.value_of("owner").unwrap().to_string();
-        }
-        if matches.is_present("repo") {
-            note_config.repo = matches.value_of("repo").unwrap().to_string();
-        }
-
+        let note_config = load_note_config(matches);
         let note_component = NoteComponent::new(Arc::new(note_config));
         let output = progress!("Creating the note", note_component.create_note().await?);
 
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+
+use crate::cmd::note::{create_config_arg, load_note_config};
+use crate::cmd::CommandTrait;
+use crate::component::note::{NoteComponent, NoteComponentTrait};
+use crate::result::CmdResult;
+
+pub const CMD_FINALIZE_NOTE: &str = "finalize";
+
+pub struct FinalizeNoteCommand;
+
+impl FinalizeNoteCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for FinalizeNoteCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_FINALIZE_NOTE)
+            .about("Comment and label the issues included in the release note")
+            .visible_alias("f")
+            .args([
+                create_config_arg(),
+                Arg::new("release")
+                    .value_name("tag")
+                    .help("Released tag, e.g. v1.2.0")
+                    .required(true)
+                    .takes_value(true),
+            ])
+    }
+
+    fn validate(&self, _matches: &ArgMatches) -> CmdResult {
+        Ok(())
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        let note_config = load_note_config(matches);
+        let release = matches.value_of("release").unwrap();
+
+        let note_component = NoteComponent::new(Arc::new(note_config));
+        let dry_run = matches.is_present("dry-run");
+        let issues = progress!(
+            format!("Finalizing the note of the release {}", release),
+            note_component.finalize_note(release, dry_run).await?
+        );
+
+        if dry_run {
+            println!(
+                "Dry run: issues {:?} of the release {} would be finalized",
+                issues, release
+            );
+            return Ok(());
+        }
+
+        println!(
+            "Successfully finalized issues {:?} of the release {}",
+            issues, release
+        );
+        Ok(())
+    }
+}
@@ -1,14 +1,20 @@
+use std::fs::File;
+use std::path::Path;
+
 use async_trait::async_trait;
-use clap::ArgMatches;
+use clap::{Arg, ArgMatches};
 
 pub use create::*;
+pub use finalize::*;
 
 use crate::cmd::note::config::{NodeConfigCommand, CMD_NODE_CONFIG};
 use crate::cmd::{create_cmd, CommandSetting, CommandTrait};
+use crate::config::NoteConfig;
 use crate::result::CmdResult;
 
 mod config;
 mod create;
+mod finalize;
 
 pub const CMD_NOTE: &str = "note";
 
@@ -25,6 +31,7 @@ impl NoteCommand {
                 commands: hashmap! {
                     CMD_CREATE_NOTE => create_cmd(Box::new(CreateNoteCommand::new())),
                     CMD_NODE_CONFIG => create_cmd(Box::new(NodeConfigCommand::new())),
+                    CMD_FINALIZE_NOTE => create_cmd(Box::new(FinalizeNoteCommand::new())),
                 },
             },
         }
@@ -41,3 +48,29 @@ impl CommandTrait for NoteCommand {
         Ok(())
     }
 }
+
+fn create_config_arg<'help>() -> Arg<'help> {
+    Arg::new("config")
+        .help("Issue search config yaml file")
+        .long("config")
+        .required(true)
+        .takes_value(true)
+}
+
+fn load_note_config(matches: &ArgMatches) -> NoteConfig {
+    let config_path = matches.value_of("config").unwrap();
+    let file = File::open(Path::new(config_path))
+        .unwrap_or_else(|_| panic!("expect {} found", config_path));
+    let mut note_config: NoteConfig =
+        serde_yaml::from_reader(file).expect("expect node config file");
+
+    // override by the global settings
+    if matches.is_present("owner") {
+        note_config.owner = matches.value_of("owner").unwrap().to_string();
+    }
+    if matches.is_present("repo") {
+        note_config.repo = matches.value_of("repo").unwrap().to_string();
+    }
+
+    note_config
+}
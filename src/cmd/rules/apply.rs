@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+use std::io::stderr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+use libcli_rs::output::{OutputFactory, OutputFormat, OutputTrait};
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+use serde::{Deserialize, Serialize};
+
+use crate::cmd::rules::{load_rules, RuleEvaluator};
+use crate::cmd::CommandTrait;
+use crate::component::repo::issue::{IssueComponentTrait, IssueUpdate};
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::result::CmdResult;
+use crate::util::get_output_format_from_args;
+
+pub const CMD_APPLY_RULES: &str = "apply";
+
+pub struct ApplyRulesCommand;
+
+#[derive(Clone, Serialize, Deserialize)]
+struct RuleReport {
+    rule: String,
+    query: String,
+    issues: Vec<u64>,
+}
+
+impl ApplyRulesCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for ApplyRulesCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_APPLY_RULES)
+            .about("Apply triage rules to issues")
+            .args([
+                Arg::new("file")
+                    .value_name("file")
+                    .help("Triage rule yaml file")
+                    .long("file")
+                    .required(true)
+                    .takes_value(true),
+                Arg::new("rules")
+                    .value_name("rules")
+                    .help("Rules to apply separated by comma [default: all rules]")
+                    .long("rules")
+                    .value_delimiter(',')
+                    .takes_value(true),
+            ])
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        let config = NoteConfig::new(matches);
+        let repo_component = RepoComponent::new(None, Arc::new(config));
+
+        let mut rules = load_rules(matches.value_of("file").unwrap())?;
+        if let Some(names) = matches.values_of("rules") {
+            let names: Vec<_> = names.collect();
+            rules.retain(|it| names.contains(&it.name.as_str()));
+        }
+
+        let mut evaluator = RuleEvaluator::new(&repo_component);
+        let mut issues_to_update: Vec<IssueUpdate> = vec![];
+        let mut issue_indexes: HashMap<u64, usize> = HashMap::new();
+        let mut reports = vec![];
+
+        for rule in &rules {
+            let issues = progress!(
+                format!("Searching issues of the rule {}", rule.name),
+                repo_component.search_all_issues_by_query(rule.query()).await?;
+            );
+
+            let mut report = RuleReport {
+                rule: rule.name.clone(),
+                query: rule.query().to_string(),
+                issues: vec![],
+            };
+
+            for issue in issues.iter().filter(|it| rule.matches(it)) {
+                let index = *issue_indexes.entry(issue.number).or_insert_with(|| {
+                    issues_to_update.push(IssueUpdate::new(issue));
+                    issues_to_update.len() - 1
+                });
+
+                evaluator
+                    .apply(rule, issue, &mut issues_to_update[index])
+                    .await?;
+                report.issues.push(issue.number);
+            }
+
+            reports.push(report);
+        }
+
+        print_rule_reports(matches, &reports)?;
+
+        self.process_issue_updates(
+            matches,
+            &repo_component,
+            issues_to_update,
+            "by the triage rules",
+        )
+        .await
+    }
+}
+
+/// Prints the rule reports, to stderr for the structured formats to keep the issue update
+/// summary the only document on stdout.
+fn print_rule_reports(matches: &ArgMatches, reports: &[RuleReport]) -> CmdResult {
+    let output_format = get_output_format_from_args(matches)?;
+    if !matches!(output_format, OutputFormat::Console) {
+        return output!(output_format, .display(stderr(), &reports.to_vec(), None, None));
+    }
+
+    for report in reports {
+        println!(
+            "Rule {} ({}): {} issues matched {:?}",
+            report.rule,
+            report.query,
+            report.issues.len(),
+            report.issues
+        );
+    }
+
+    Ok(())
+}
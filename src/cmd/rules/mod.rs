@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+use std::fs::File;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use hubcaps_ex::issues::Issue;
+use serde::Deserialize;
+
+pub use apply::{ApplyRulesCommand, CMD_APPLY_RULES};
+
+use crate::cmd::issue::{create_comment_marker, render_issue_comment, AssigneeRotation};
+use crate::cmd::label::is_label_of_family;
+use crate::cmd::{create_cmd, CommandSetting, CommandTrait};
+use crate::component::repo::issue::{IssueComponentTrait, IssueMilestone, IssueUpdate};
+use crate::component::repo::milestone::MilestoneComponentTrait;
+use crate::result::Result;
+
+mod apply;
+
+pub const CMD_RULES: &str = "rules";
+
+const DEFAULT_RULE_QUERY: &str = "is:issue is:open";
+
+pub struct RulesCommand {
+    setting: CommandSetting,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Rules {
+    pub rules: Vec<Rule>,
+}
+
+/// A triage rule applying the actions to the issues matching the query and the predicate.
+#[derive(Debug, Deserialize)]
+pub(crate) struct Rule {
+    pub name: String,
+    /// The issue search query, `is:issue is:open` by default.
+    #[serde(default)]
+    pub query: Option<String>,
+    #[serde(default)]
+    pub when: RulePredicate,
+    pub actions: RuleActions,
+}
+
+/// The local predicate evaluated against the searched issues. Labels are label families like `kind/*`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct RulePredicate {
+    pub labels: Vec<String>,
+    pub no_labels: Vec<String>,
+    pub state: Option<String>,
+    pub assigned: Option<bool>,
+    pub milestone: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct RuleActions {
+    pub add_labels: Vec<String>,
+    pub remove_labels: Vec<String>,
+    pub milestone: Option<String>,
+    pub assign: Vec<String>,
+    /// Assigns unassigned issues to one of the users as a triage rotation.
+    pub assign_one_of: Vec<String>,
+    pub comment: Option<String>,
+    /// Closes issues with the reason, `completed` or `not_planned`.
+    pub close: Option<String>,
+}
+
+impl RulesCommand {
+    pub fn new() -> Self {
+        RulesCommand {
+            setting: CommandSetting {
+                name: CMD_RULES,
+                about: "Triage rule commands",
+                commands: hashmap! {
+                    CMD_APPLY_RULES => create_cmd(Box::new(ApplyRulesCommand::new())),
+                },
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl CommandTrait for RulesCommand {
+    fn setting(&self) -> &CommandSetting {
+        &self.setting
+    }
+}
+
+impl Rule {
+    pub fn query(&self) -> &str {
+        self.query.as_deref().unwrap_or(DEFAULT_RULE_QUERY)
+    }
+
+    pub fn matches(&self, issue: &Issue) -> bool {
+        let when = &self.when;
+        let has_label = |family: &String| {
+            issue
+                .labels
+                .iter()
+                .any(|it| is_label_of_family(&it.name, family))
+        };
+        let assigned = !issue.assignees.is_empty();
+
+        when.labels.iter().all(has_label)
+            && !when.no_labels.iter().any(has_label)
+            && when.state.as_ref().map_or(true, |it| *it == issue.state)
+            && when.assigned.map_or(true, |it| it == assigned)
+            && when
+                .milestone
+                .map_or(true, |it| it == issue.milestone.is_some())
+    }
+}
+
+pub(crate) fn load_rules(path: &str) -> Result<Vec<Rule>> {
+    let file = File::open(path).map_err(|err| anyhow!("failed to open {}: {}", path, err))?;
+    let rules: Rules = serde_yaml::from_reader(file)
+        .map_err(|err| anyhow!("failed to parse {}: {}", path, err))?;
+
+    Ok(rules.rules)
+}
+
+/// Applies the rule actions to the issue updates, caching the milestones and rotations across issues.
+pub(crate) struct RuleEvaluator<'a, R> {
+    repo_component: &'a R,
+    milestones: HashMap<String, IssueMilestone>,
+    rotations: HashMap<String, AssigneeRotation>,
+}
+
+impl<'a, R> RuleEvaluator<'a, R>
+where
+    R: IssueComponentTrait + MilestoneComponentTrait + Send + Sync,
+{
+    pub fn new(repo_component: &'a R) -> Self {
+        RuleEvaluator {
+            repo_component,
+            milestones: HashMap::new(),
+            rotations: HashMap::new(),
+        }
+    }
+
+    /// Applies the rule actions to the issue, the predicate is expected to be checked by the caller.
+    pub async fn apply(
+        &mut self,
+        rule: &Rule,
+        issue: &Issue,
+        update: &mut IssueUpdate,
+    ) -> Result<()> {
+        let actions = &rule.actions;
+
+        update.add_labels(
+            &actions
+                .add_labels
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>(),
+        );
+        update.remove_labels(
+            &actions
+                .remove_labels
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>(),
+        );
+        update.add_assignees(
+            &actions
+                .assign
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>(),
+        );
+
+        if let Some(milestone) = &actions.milestone {
+            if !self.milestones.contains_key(milestone) {
+                let found = self.repo_component.find_milestone(milestone).await?;
+                self.milestones
+                    .insert(milestone.clone(), IssueMilestone::from(&found));
+            }
+            update.after.milestone = self.milestones.get(milestone).cloned();
+        }
+
+        if !actions.assign_one_of.is_empty() && update.after.assignees.is_empty() {
+            if !self.rotations.contains_key(&rule.name) {
+                let users: Vec<_> = actions.assign_one_of.iter().map(String::as_str).collect();
+                let rotation = AssigneeRotation::new(self.repo_component, &users).await?;
+                self.rotations.insert(rule.name.clone(), rotation);
+            }
+            if let Some(user) = self.rotations.get_mut(&rule.name).and_then(|it| it.next()) {
+                update.add_assignees(&[user]);
+            }
+        }
+
+        if let Some(template) = &actions.comment {
+            let marker = create_comment_marker(&format!("rule:{}", rule.name));
+            let comment =
+                render_issue_comment(self.repo_component, issue, template, &marker).await?;
+
+            // multiple rules commenting on the same issue are posted as a single comment
+            update.comment = match (update.comment.take(), comment) {
+                (Some(previous), Some(comment)) => Some(format!("{}\n\n{}", previous, comment)),
+                (previous, comment) => previous.or(comment),
+            };
+        }
+
+        if let Some(reason) = &actions.close {
+            update.after.state = "closed".to_string();
+            update.state_reason = Some(reason.clone());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,568 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+use hubcaps_ex::issues::Issue;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use log::{error, info, warn};
+use ring::hmac;
+use serde::Deserialize;
+
+use crate::cmd::label::is_same_label_name;
+use crate::cmd::rules::{load_rules, Rule, RuleEvaluator};
+use crate::cmd::{init_process, CommandTrait};
+use crate::component::repo::issue::{IssueComponentTrait, IssueUpdate};
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::journal::{save_journal_entry, JournalEntry};
+use crate::result::{CmdResult, Result};
+
+pub const CMD_SERVE: &str = "serve";
+
+const SIGNATURE_HEADER: &str = "X-Hub-Signature-256";
+const EVENT_HEADER: &str = "X-GitHub-Event";
+const DELIVERY_HEADER: &str = "X-GitHub-Delivery";
+
+pub struct ServeCommand;
+
+struct WebhookState {
+    key: hmac::Key,
+    token: String,
+    rules: Vec<Rule>,
+    dry_run: bool,
+}
+
+/// The fields of `issues` and `pull_request` webhook payloads needed to apply the rules.
+#[derive(Deserialize)]
+struct WebhookPayload {
+    action: String,
+    #[serde(default)]
+    issue: Option<WebhookIssue>,
+    #[serde(default)]
+    pull_request: Option<WebhookIssue>,
+    repository: WebhookRepository,
+}
+
+#[derive(Deserialize)]
+struct WebhookIssue {
+    number: u64,
+}
+
+#[derive(Deserialize)]
+struct WebhookRepository {
+    name: String,
+    owner: WebhookOwner,
+}
+
+#[derive(Deserialize)]
+struct WebhookOwner {
+    login: String,
+}
+
+impl ServeCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for ServeCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_SERVE)
+            .about("Serve GitHub webhook deliveries to apply triage rules on issue events")
+            .args([
+                Arg::new("rules")
+                    .value_name("file")
+                    .help("Triage rule yaml file")
+                    .long("rules")
+                    .required(true)
+                    .takes_value(true),
+                Arg::new("secret")
+                    .value_name("string")
+                    .help("Webhook secret to verify the delivery signatures")
+                    .long("secret")
+                    .env("GITHUB_WEBHOOK_SECRET")
+                    .required(true)
+                    .takes_value(true),
+                Arg::new("address")
+                    .value_name("address")
+                    .help("Address to listen on")
+                    .long("address")
+                    .takes_value(true)
+                    .default_value("127.0.0.1:8080"),
+            ])
+    }
+
+    fn validate(&self, matches: &ArgMatches) -> CmdResult {
+        if matches.is_present("token") {
+            return Ok(());
+        }
+
+        Err(anyhow!("GitHub token is mandatory"))
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        init_process(matches)?;
+        self.validate(matches)?;
+
+        let address: SocketAddr = matches.value_of_t("address")?;
+        let state = Arc::new(WebhookState {
+            key: hmac::Key::new(
+                hmac::HMAC_SHA256,
+                matches.value_of("secret").unwrap().as_bytes(),
+            ),
+            token: matches.value_of("token").unwrap().to_string(),
+            rules: load_rules(matches.value_of("rules").unwrap())?,
+            dry_run: matches.is_present("dry-run"),
+        });
+
+        let make_service = make_service_fn(move |_| {
+            let state = state.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    handle_delivery(state.clone(), request)
+                }))
+            }
+        });
+
+        println!("Listening on {} for GitHub webhook deliveries", address);
+        Server::try_bind(&address)?
+            .serve(make_service)
+            .with_graceful_shutdown(async {
+                tokio::signal::ctrl_c().await.ok();
+            })
+            .await?;
+
+        Ok(())
+    }
+}
+
+async fn handle_delivery(
+    state: Arc<WebhookState>,
+    request: Request<Body>,
+) -> std::result::Result<Response<Body>, Infallible> {
+    if request.method() != Method::POST {
+        return Ok(create_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            "method not allowed",
+        ));
+    }
+
+    let header = |name: &str| {
+        request
+            .headers()
+            .get(name)
+            .and_then(|it| it.to_str().ok())
+            .map(String::from)
+    };
+    let signature = header(SIGNATURE_HEADER);
+    let event = header(EVENT_HEADER).unwrap_or_default();
+    let delivery = header(DELIVERY_HEADER).unwrap_or_default();
+
+    let body = match hyper::body::to_bytes(request.into_body()).await {
+        Ok(body) => body,
+        Err(err) => {
+            warn!("delivery {}: failed to read the body: {}", delivery, err);
+            return Ok(create_response(StatusCode::BAD_REQUEST, "invalid body"));
+        }
+    };
+
+    if !verify_signature(&state.key, signature.as_deref(), &body) {
+        warn!("delivery {}: invalid signature", delivery);
+        return Ok(create_response(
+            StatusCode::UNAUTHORIZED,
+            "invalid signature",
+        ));
+    }
+
+    if !matches!(event.as_str(), "issues" | "pull_request") {
+        info!("delivery {}: {} event ignored", delivery, event);
+        return Ok(create_response(StatusCode::ACCEPTED, "ignored"));
+    }
+
+    let payload: WebhookPayload = match serde_json::from_slice(&body) {
+        Ok(payload) => payload,
+        Err(err) => {
+            warn!("delivery {}: invalid payload: {}", delivery, err);
+            return Ok(create_response(StatusCode::BAD_REQUEST, "invalid payload"));
+        }
+    };
+
+    let number = match payload.issue.as_ref().or(payload.pull_request.as_ref()) {
+        Some(issue) if !matches!(payload.action.as_str(), "deleted" | "transferred") => {
+            issue.number
+        }
+        _ => {
+            info!(
+                "delivery {}: {} {} ignored",
+                delivery, event, payload.action
+            );
+            return Ok(create_response(StatusCode::ACCEPTED, "ignored"));
+        }
+    };
+
+    info!(
+        "delivery {}: {} {} of {}/{}#{}",
+        delivery,
+        event,
+        payload.action,
+        payload.repository.owner.login,
+        payload.repository.name,
+        number
+    );
+
+    // respond right away, as GitHub expects the response in 10 seconds
+    tokio::spawn(async move {
+        let result = apply_rules(
+            &state,
+            &payload.repository,
+            number,
+            payload.pull_request.is_some(),
+        )
+        .await;
+
+        if let Err(err) = result {
+            error!("delivery {}: failed to apply rules: {}", delivery, err);
+        }
+    });
+
+    Ok(create_response(StatusCode::ACCEPTED, "accepted"))
+}
+
+/// Applies the matched rules to the issue, which doesn't loop on the events of its own updates, as
+/// the actions are idempotent.
+async fn apply_rules(
+    state: &WebhookState,
+    repository: &WebhookRepository,
+    number: u64,
+    is_pull_request: bool,
+) -> Result<()> {
+    let config = NoteConfig {
+        owner: repository.owner.login.clone(),
+        repo: repository.name.clone(),
+        token: state.token.clone(),
+        ..Default::default()
+    };
+    let repo_component = RepoComponent::new(None, Arc::new(config));
+
+    let issue = repo_component.get_issue(number).await?;
+    let mut evaluator = RuleEvaluator::new(&repo_component);
+    let mut update = IssueUpdate::new(&issue);
+
+    for rule in &state.rules {
+        if !rule.matches(&issue) {
+            continue;
+        }
+
+        let matched = match match_query_locally(rule.query(), &issue, is_pull_request) {
+            Some(matched) => matched,
+            None => repo_component
+                .search_all_issues_by_query(rule.query())
+                .await?
+                .iter()
+                .any(|it| it.number == number),
+        };
+        if !matched {
+            continue;
+        }
+
+        info!("#{}: matched the rule {}", number, rule.name);
+        evaluator.apply(rule, &issue, &mut update).await?;
+    }
+
+    if !update.is_changed() {
+        info!("#{}: no changes", number);
+        return Ok(());
+    }
+
+    for action in describe_issue_update(&update) {
+        info!(
+            "#{}: {}{}",
+            number,
+            action,
+            if state.dry_run { " (dry run)" } else { "" }
+        );
+    }
+    if state.dry_run {
+        return Ok(());
+    }
+
+    repo_component.update_issue(&update).await?;
+
+    let path = save_journal_entry(&JournalEntry::new(
+        &repository.owner.login,
+        &repository.name,
+        vec![update],
+        None,
+    ))?;
+    info!("#{}: updated, journal: {}", number, path.display());
+
+    Ok(())
+}
+
+/// Evaluates the search query for the common qualifiers locally, as the search index lags behind
+/// the events. Returns None if the query has any other term, to fall back to the search.
+fn match_query_locally(query: &str, issue: &Issue, is_pull_request: bool) -> Option<bool> {
+    let mut matched = true;
+
+    for term in split_query(query) {
+        let (negated, term) = match term.strip_prefix('-') {
+            Some(term) => (true, term),
+            None => (false, term.as_str()),
+        };
+        let (qualifier, value) = term.split_once(':')?;
+        let value = value.trim_matches('"');
+
+        let result = match (qualifier, value) {
+            ("is", "open" | "closed") => issue.state == value,
+            ("is", "issue") => !is_pull_request,
+            ("is", "pr") => is_pull_request,
+            ("no", "assignee") => issue.assignees.is_empty(),
+            ("no", "milestone") => issue.milestone.is_none(),
+            ("no", "label") => issue.labels.is_empty(),
+            ("label", _) => value.split(',').any(|label| {
+                issue
+                    .labels
+                    .iter()
+                    .any(|it| is_same_label_name(&it.name, label))
+            }),
+            ("assignee", _) => issue.assignees.iter().any(|it| it.login == value),
+            ("milestone", _) => issue
+                .milestone
+                .as_ref()
+                .map_or(false, |it| it.title == value),
+            _ => return None,
+        };
+
+        matched &= result != negated;
+    }
+
+    Some(matched)
+}
+
+/// Splits the search query by whitespaces, except the ones in quotes.
+fn split_query(query: &str) -> Vec<String> {
+    let mut terms = vec![];
+    let mut term = String::new();
+    let mut quoted = false;
+
+    for c in query.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                term.push(c);
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !term.is_empty() {
+                    terms.push(std::mem::take(&mut term));
+                }
+            }
+            c => term.push(c),
+        }
+    }
+    if !term.is_empty() {
+        terms.push(term);
+    }
+
+    terms
+}
+
+fn describe_issue_update(update: &IssueUpdate) -> Vec<String> {
+    let mut actions = vec![];
+
+    if !update.added_labels().is_empty() {
+        actions.push(format!("add labels {:?}", update.added_labels()));
+    }
+    if !update.removed_labels().is_empty() {
+        actions.push(format!("remove labels {:?}", update.removed_labels()));
+    }
+    if !update.added_assignees().is_empty() {
+        actions.push(format!("assign {:?}", update.added_assignees()));
+    }
+    if !update.removed_assignees().is_empty() {
+        actions.push(format!("unassign {:?}", update.removed_assignees()));
+    }
+    if update.before.milestone != update.after.milestone {
+        actions.push(format!(
+            "set milestone {}",
+            update
+                .after
+                .milestone
+                .as_ref()
+                .map_or("N/A", |it| it.title.as_str())
+        ));
+    }
+    if update.before.state != update.after.state {
+        actions.push(format!(
+            "set state {} ({})",
+            update.after.state,
+            update.state_reason.as_deref().unwrap_or("N/A")
+        ));
+    }
+    if let Some(comment) = &update.comment {
+        actions.push(format!("comment {:?}", comment));
+    }
+
+    actions
+}
+
+fn verify_signature(key: &hmac::Key, signature: Option<&str>, body: &[u8]) -> bool {
+    let tag = signature
+        .and_then(|it| it.strip_prefix("sha256="))
+        .and_then(decode_hex);
+
+    match tag {
+        Some(tag) => hmac::verify(key, body, &tag).is_ok(),
+        None => false,
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn create_response(status: StatusCode, body: &str) -> Response<Body> {
+    let mut response = Response::new(Body::from(body.to_string()));
+    *response.status_mut() = status;
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &[u8] = b"renote-webhook-secret";
+    const ISSUES_LABELED: &[u8] =
+        include_bytes!("../../tests/fixtures/webhooks/issues_labeled.json");
+    const ISSUES_LABELED_SIGNATURE: &str =
+        include_str!("../../tests/fixtures/webhooks/issues_labeled.sha256");
+
+    fn key() -> hmac::Key {
+        hmac::Key::new(hmac::HMAC_SHA256, SECRET)
+    }
+
+    fn labeled_issue() -> Issue {
+        let payload: serde_json::Value = serde_json::from_slice(ISSUES_LABELED).unwrap();
+        serde_json::from_value(payload["issue"].clone()).unwrap()
+    }
+
+    #[test]
+    fn verify_signature_of_recorded_payload() {
+        let signature = ISSUES_LABELED_SIGNATURE.trim();
+
+        assert!(verify_signature(&key(), Some(signature), ISSUES_LABELED));
+        assert!(!verify_signature(&key(), Some(signature), b"{}"));
+        assert!(!verify_signature(
+            &hmac::Key::new(hmac::HMAC_SHA256, b"other-secret"),
+            Some(signature),
+            ISSUES_LABELED
+        ));
+    }
+
+    #[test]
+    fn verify_invalid_signature() {
+        assert!(!verify_signature(&key(), None, ISSUES_LABELED));
+        assert!(!verify_signature(&key(), Some("sha1=abc"), ISSUES_LABELED));
+        assert!(!verify_signature(
+            &key(),
+            Some("sha256=xyz"),
+            ISSUES_LABELED
+        ));
+        assert!(!verify_signature(
+            &key(),
+            Some("sha256=abc"),
+            ISSUES_LABELED
+        ));
+    }
+
+    #[test]
+    fn parse_recorded_payload() {
+        let payload: WebhookPayload = serde_json::from_slice(ISSUES_LABELED).unwrap();
+
+        assert_eq!(payload.action, "labeled");
+        assert_eq!(payload.issue.map(|it| it.number), Some(3072));
+        assert!(payload.pull_request.is_none());
+        assert_eq!(payload.repository.owner.login, "longhorn");
+        assert_eq!(payload.repository.name, "longhorn");
+    }
+
+    #[test]
+    fn split_query_by_whitespaces() {
+        assert_eq!(
+            split_query("is:open  label:kind/bug\tno:milestone"),
+            vec!["is:open", "label:kind/bug", "no:milestone"]
+        );
+        assert_eq!(
+            split_query(r#"is:issue label:"area/volume attach" -label:wontfix"#),
+            vec![
+                "is:issue",
+                r#"label:"area/volume attach""#,
+                "-label:wontfix"
+            ]
+        );
+        assert!(split_query("  ").is_empty());
+    }
+
+    #[test]
+    fn match_query_locally_by_qualifiers() {
+        let issue = labeled_issue();
+
+        assert_eq!(match_query_locally("", &issue, false), Some(true));
+        assert_eq!(
+            match_query_locally("is:issue is:open label:kind/bug", &issue, false),
+            Some(true)
+        );
+        assert_eq!(
+            match_query_locally("label:kind/*", &issue, false),
+            Some(false)
+        );
+        assert_eq!(
+            match_query_locally("label:kind/feature,kind/bug", &issue, false),
+            Some(true)
+        );
+        assert_eq!(
+            match_query_locally("no:assignee no:milestone", &issue, false),
+            Some(true)
+        );
+        assert_eq!(match_query_locally("is:closed", &issue, false), Some(false));
+        assert_eq!(match_query_locally("is:pr", &issue, false), Some(false));
+        assert_eq!(match_query_locally("is:issue", &issue, true), Some(false));
+        assert_eq!(
+            match_query_locally("-label:kind/bug", &issue, false),
+            Some(false)
+        );
+        assert_eq!(match_query_locally("no:label", &issue, false), Some(false));
+        assert_eq!(
+            match_query_locally("assignee:innobead", &issue, false),
+            Some(false)
+        );
+        assert_eq!(
+            match_query_locally(r#"milestone:"v1.3.0""#, &issue, false),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn match_query_locally_falls_back_to_search() {
+        let issue = labeled_issue();
+
+        assert_eq!(
+            match_query_locally("is:open attaching", &issue, false),
+            None
+        );
+        assert_eq!(match_query_locally("author:innobead", &issue, false), None);
+        assert_eq!(match_query_locally("is:draft", &issue, false), None);
+    }
+}
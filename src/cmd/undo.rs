@@ -0,0 +1,100 @@
+use std::io::stdout;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+use libcli_rs::output::{OutputFactory, OutputTrait};
+use libcli_rs::progress::{ProgressBar, ProgressTrait};
+
+use crate::cmd::{init_process, update_issues, CommandTrait, IssueUpdateOptions};
+use crate::component::repo::RepoComponent;
+use crate::config::NoteConfig;
+use crate::journal::{get_latest_journal_entry, list_journal_entries, load_journal_entry};
+use crate::result::CmdResult;
+use crate::util::get_output_format_from_args;
+
+pub const CMD_UNDO: &str = "undo";
+
+pub struct UndoCommand;
+
+impl UndoCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CommandTrait for UndoCommand {
+    fn app<'help>(&self) -> Command<'help> {
+        Command::new(CMD_UNDO)
+            .about("Undo a bulk issue update recorded in the journal")
+            .args([
+                Arg::new("journal")
+                    .value_name("journal-id")
+                    .help("Journal ID, the latest journal by default")
+                    .takes_value(true),
+                Arg::new("list").help("List the journal").long("list"),
+            ])
+    }
+
+    fn validate(&self, matches: &ArgMatches) -> CmdResult {
+        if matches.is_present("token") {
+            return Ok(());
+        }
+
+        Err(anyhow!("GitHub token is mandatory"))
+    }
+
+    async fn process(&self, matches: &ArgMatches) -> CmdResult {
+        init_process(matches)?;
+
+        if matches.is_present("list") {
+            let entries = list_journal_entries()?;
+            if entries.is_empty() {
+                progress!("Journal not found", ());
+                return Ok(());
+            }
+
+            let output_format = get_output_format_from_args(matches)?;
+            return output!(output_format, .display(
+                stdout(),
+                &entries,
+                Some(vec![
+                    "id", "timestamp", "owner", "repo", "command", "undo_of", "undone",
+                ]),
+                None,
+            ));
+        }
+
+        self.validate(matches)?;
+
+        let entry = match matches.value_of("journal") {
+            Some(id) => load_journal_entry(id)?,
+            None => get_latest_journal_entry()?,
+        };
+        if entry.undone {
+            return Err(anyhow!("journal {} already undone", entry.id));
+        }
+
+        let config = NoteConfig {
+            owner: entry.owner.clone(),
+            repo: entry.repo.clone(),
+            token: matches.value_of("token").unwrap().to_string(),
+            ..Default::default()
+        };
+        let repo_component = RepoComponent::new(None, Arc::new(config));
+
+        update_issues(
+            matches,
+            &repo_component,
+            entry.to_undo_updates(),
+            &format!("to undo the journal {} ({})", entry.id, entry.command),
+            IssueUpdateOptions {
+                undo_of: Some(entry.id.clone()),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+}
@@ -2,18 +2,24 @@ use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::sync::Arc;
 
+use anyhow::anyhow;
 use async_trait::async_trait;
 use hubcaps_ex::issues::Issue;
 use log::{debug, info, trace};
 use serde::{Deserialize, Serialize};
 use tera::{from_value, to_value, Context, Tera, Value};
 
-use crate::component::repo::issue::IssueComponentTrait;
+use crate::component::repo::issue::{
+    IssueComponentTrait, IssueUpdate, IssueUpdateStatus, DEFAULT_UPDATE_CONCURRENCY,
+};
+use crate::component::repo::release::ReleaseComponentTrait;
 use crate::component::repo::RepoComponent;
 use crate::config::NoteConfig;
 use crate::result::Result;
 use crate::util::create_github_client;
 
+const DEFAULT_RELEASE_LABEL: &str = "released/{version}";
+
 const ISSUE_SECTION_TEMPLATE: &'static str = r#"
 {% for section in sections %}
 ## {{ section.title }}
@@ -77,6 +83,8 @@ fn assignees_str(args: &HashMap<String, Value>) -> tera::Result<Value> {
 #[async_trait]
 pub trait NoteComponentTrait {
     async fn create_note(&self) -> Result<String>;
+    async fn list_note_issues(&self) -> Result<Vec<Issue>>;
+    async fn finalize_note(&self, release_tag: &str, dry_run: bool) -> Result<Vec<u64>>;
     fn render_note(&self, issues: &Vec<Issue>) -> Result<String>;
 }
 
@@ -95,11 +103,101 @@ impl NoteComponentTrait for NoteComponent {
     async fn create_note(&self) -> Result<String> {
         info!("creating note");
 
+        let issues = self.list_note_issues().await?;
+        self.render_note(&issues)
+    }
+
+    async fn list_note_issues(&self) -> Result<Vec<Issue>> {
         let github = Arc::new(create_github_client(&self.config.token)?);
         let repo_component = RepoComponent::new(Some(github), self.config.clone());
 
-        let issues = repo_component.list_issues().await?;
-        self.render_note(&issues)
+        repo_component.list_issues().await
+    }
+
+    async fn finalize_note(&self, release_tag: &str, dry_run: bool) -> Result<Vec<u64>> {
+        info!(
+            "finalizing note: release: {}, dry run: {}",
+            release_tag, dry_run
+        );
+
+        let github = Arc::new(create_github_client(&self.config.token)?);
+        let repo_component = RepoComponent::new(Some(github.clone()), self.config.clone());
+
+        let release = repo_component.get_release_by_tag(release_tag).await?;
+        // the latest release is the finalized one after publishing, so the issues are listed
+        // since the previous release instead
+        let note_config = match self.config.since {
+            Some(_) => self.config.clone(),
+            None => {
+                let previous_release = repo_component
+                    .get_previous_release(&release)
+                    .await?
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "no release found before {}, set `since` in the config",
+                            release_tag
+                        )
+                    })?;
+
+                Arc::new(NoteConfig {
+                    since: Some(previous_release.created_at),
+                    ..(*self.config).clone()
+                })
+            }
+        };
+        let note_issues = RepoComponent::new(Some(github), note_config)
+            .list_issues()
+            .await?;
+        let comment = format!("Released in {}: {}", release.tag_name, release.html_url);
+        let label = self
+            .config
+            .release_label
+            .as_deref()
+            .unwrap_or(DEFAULT_RELEASE_LABEL)
+            .replace("{version}", &release.tag_name);
+
+        let mut finalized_issues = HashSet::new();
+        let mut issues_to_update = vec![];
+
+        for issue in note_issues {
+            if !finalized_issues.insert(issue.number) || dry_run {
+                continue;
+            }
+
+            let comments = repo_component.list_comments(issue.number).await?;
+            if comments.iter().any(|it| it.body.trim() == comment) {
+                debug!("issue {} already commented, skipped", issue.number);
+            } else {
+                repo_component
+                    .create_comment(issue.number, &comment)
+                    .await?;
+            }
+
+            if issue.labels.iter().any(|it| it.name == label) {
+                debug!("issue {} already labeled, skipped", issue.number);
+                continue;
+            }
+
+            let mut issue_to_update = IssueUpdate::new(&issue);
+            issue_to_update.add_labels(&[label.as_str()]);
+            issues_to_update.push(issue_to_update);
+        }
+
+        let failed_issues: Vec<_> = repo_component
+            .update_issues(&issues_to_update, DEFAULT_UPDATE_CONCURRENCY)
+            .await
+            .into_iter()
+            .filter(|it| it.status == IssueUpdateStatus::Failed)
+            .map(|it| it.number)
+            .collect();
+        if !failed_issues.is_empty() {
+            return Err(anyhow!("failed to label issues {:?}", failed_issues));
+        }
+
+        let mut finalized_issues: Vec<_> = finalized_issues.into_iter().collect();
+        finalized_issues.sort();
+
+        Ok(finalized_issues)
     }
 
     fn render_note(&self, issues: &Vec<Issue>) -> Result<String> {
@@ -1,37 +1,286 @@
 use anyhow::anyhow;
 use async_trait::async_trait;
-use hubcaps_ex::issues::{Issue, IssueListOptions, IssueOptions, Sort, State};
+use futures::future::join_all;
+use hubcaps_ex::comments::{Comment, CommentOptions};
+use hubcaps_ex::issues::Issue;
 use hubcaps_ex::milestone::{Milestone, MilestoneListOptions};
-use hubcaps_ex::search::{IssuesItem, IssuesSort, SearchIssuesOptions};
-use log::{debug, error, info, trace};
-use tokio_stream::StreamExt;
+use log::{debug, error, info, trace, warn};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Semaphore;
 
 use crate::component::repo::release::ReleaseComponentTrait;
 use crate::component::repo::RepoComponent;
 use crate::config::IssueSort;
+use crate::rest::encode_path_segment;
 use crate::result::Result;
+use crate::util::retry;
+
+/// The issue fields which can be changed by the bulk update commands.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IssueSnapshot {
+    pub state: String,
+    pub labels: Vec<String>,
+    pub milestone: Option<IssueMilestone>,
+    pub assignees: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IssueMilestone {
+    pub number: u64,
+    pub title: String,
+}
+
+/// The change of an issue, from the state when searched to the expected state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueUpdate {
+    pub number: u64,
+    pub title: String,
+    pub before: IssueSnapshot,
+    pub after: IssueSnapshot,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+impl From<&Issue> for IssueSnapshot {
+    fn from(issue: &Issue) -> Self {
+        IssueSnapshot {
+            state: issue.state.clone(),
+            labels: issue.labels.iter().map(|it| it.name.clone()).collect(),
+            milestone: issue.milestone.as_ref().map(|it| IssueMilestone {
+                number: it.number,
+                title: it.title.clone(),
+            }),
+            assignees: issue.assignees.iter().map(|it| it.login.clone()).collect(),
+        }
+    }
+}
+
+impl From<&Milestone> for IssueMilestone {
+    fn from(milestone: &Milestone) -> Self {
+        IssueMilestone {
+            number: milestone.number,
+            title: milestone.title.clone(),
+        }
+    }
+}
+
+impl IssueUpdate {
+    pub fn new(issue: &Issue) -> Self {
+        let snapshot = IssueSnapshot::from(issue);
+
+        IssueUpdate {
+            number: issue.number,
+            title: issue.title.clone(),
+            before: snapshot.clone(),
+            after: snapshot,
+            state_reason: None,
+            comment: None,
+        }
+    }
+
+    pub fn add_labels(&mut self, labels: &[&str]) {
+        for label in labels {
+            if !self.after.labels.iter().any(|it| it == label) {
+                self.after.labels.push(label.to_string());
+            }
+        }
+    }
+
+    pub fn remove_labels(&mut self, labels: &[&str]) {
+        self.after
+            .labels
+            .retain(|it| !labels.contains(&it.as_str()));
+    }
+
+    pub fn add_assignees(&mut self, assignees: &[&str]) {
+        for assignee in assignees {
+            if !self.after.assignees.iter().any(|it| it == assignee) {
+                self.after.assignees.push(assignee.to_string());
+            }
+        }
+    }
+
+    pub fn remove_assignees(&mut self, assignees: &[&str]) {
+        self.after
+            .assignees
+            .retain(|it| !assignees.contains(&it.as_str()));
+    }
+
+    pub fn is_changed(&self) -> bool {
+        self.before != self.after || self.comment.is_some()
+    }
+
+    pub fn added_labels(&self) -> Vec<&str> {
+        difference(&self.after.labels, &self.before.labels)
+    }
 
-fn to_issue_state(state: &str) -> State {
+    pub fn removed_labels(&self) -> Vec<&str> {
+        difference(&self.before.labels, &self.after.labels)
+    }
+
+    pub fn added_assignees(&self) -> Vec<&str> {
+        difference(&self.after.assignees, &self.before.assignees)
+    }
+
+    pub fn removed_assignees(&self) -> Vec<&str> {
+        difference(&self.before.assignees, &self.after.assignees)
+    }
+
+    /// Creates the minimal issue patch only touching the changed fields.
+    fn to_patch(&self) -> IssuePatch {
+        let mut patch = IssuePatch::default();
+
+        if self.before.state != self.after.state {
+            patch.state = Some(self.after.state.clone());
+            patch.state_reason = self.state_reason.clone();
+        }
+
+        if self.before.milestone != self.after.milestone {
+            patch.milestone = Some(self.after.milestone.as_ref().map(|it| it.number));
+        }
+
+        patch
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueUpdateResult {
+    pub number: u64,
+    pub title: String,
+    pub status: IssueUpdateStatus,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum IssueUpdateStatus {
+    #[serde(rename = "succeeded")]
+    Succeeded,
+    #[serde(rename = "failed")]
+    Failed,
+    #[serde(rename = "skipped")]
+    Skipped,
+}
+
+impl IssueUpdateResult {
+    pub fn new(issue: &IssueUpdate, status: IssueUpdateStatus, reason: Option<String>) -> Self {
+        IssueUpdateResult {
+            number: issue.number,
+            title: issue.title.clone(),
+            status,
+            reason,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+struct IssuePatch {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    state_reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    milestone: Option<Option<u64>>,
+}
+
+impl IssuePatch {
+    fn is_empty(&self) -> bool {
+        self.state.is_none() && self.milestone.is_none()
+    }
+}
+
+fn difference<'a>(items: &'a [String], other_items: &[String]) -> Vec<&'a str> {
+    items
+        .iter()
+        .filter(|it| !other_items.contains(it))
+        .map(|it| it.as_str())
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueEvent {
+    pub event: String,
+    pub created_at: String,
+    pub actor: Option<IssueEventActor>,
+    pub milestone: Option<IssueEventMilestone>,
+    #[serde(default)]
+    pub label: Option<IssueEventLabel>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueEventActor {
+    pub login: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueEventMilestone {
+    pub title: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueEventLabel {
+    pub name: String,
+}
+
+fn to_issue_state(state: &str) -> &str {
     match state {
-        "open" => State::Open,
-        "closed" => State::Closed,
-        _ => State::All,
+        "open" | "closed" => state,
+        _ => "all",
     }
 }
 
-fn to_issue(issue_item: &IssuesItem) -> Result<Issue> {
-    let output = serde_yaml::to_string(issue_item)?;
-    Ok(serde_yaml::from_str::<Issue>(&output)?)
+#[derive(Debug, Default, Serialize)]
+pub struct IssueCreateOptions {
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub assignees: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct SearchIssuesResult {
+    total_count: usize,
+    items: Vec<Issue>,
 }
 
+pub const DEFAULT_UPDATE_CONCURRENCY: usize = 5;
+
+const TRANSFER_ISSUE_MUTATION: &str = "mutation($issueId: ID!, $repositoryId: ID!) { \
+    transferIssue(input: {issueId: $issueId, repositoryId: $repositoryId}) { issue { number } } }";
+
+const SEARCH_PAGE_SIZE: usize = 100;
+const SEARCH_MAX_RESULTS: usize = 1000;
+
 #[async_trait]
 pub trait IssueComponentTrait {
     async fn list_issues(&self) -> Result<Vec<Issue>>;
+    async fn get_issue(&self, number: u64) -> Result<Issue>;
+    async fn create_issue(&self, options: &IssueCreateOptions) -> Result<Issue>;
+    async fn transfer_issue(&self, number: u64, owner: &str, repo: &str) -> Result<Option<u64>>;
+    async fn list_all_issues(&self, labels: &[&str]) -> Result<Vec<Issue>>;
     async fn search_issues_by_labels(&self, labels: &[String]) -> Result<Vec<Issue>>;
     async fn search_issues_by_query(&self, query: &str) -> Result<Vec<Issue>>;
-    async fn update_issues(&self, issues: &Vec<(u64, IssueOptions)>) -> Result<()>;
+    async fn search_all_issues_by_query(&self, query: &str) -> Result<Vec<Issue>>;
+    async fn update_issues(
+        &self,
+        issues: &[IssueUpdate],
+        concurrency: usize,
+    ) -> Vec<IssueUpdateResult>;
+    async fn update_issue_with_result(&self, issue: &IssueUpdate) -> IssueUpdateResult;
+    async fn update_issue(&self, issue: &IssueUpdate) -> Result<()>;
     async fn get_milestone(&self, milestone: &str) -> Result<Milestone>;
+    async fn list_comments(&self, issue_number: u64) -> Result<Vec<Comment>>;
+    async fn create_comment(&self, issue_number: u64, body: &str) -> Result<Comment>;
+    async fn list_events_of_issue(&self, issue_number: u64) -> Result<Vec<IssueEvent>>;
+    async fn count_issues_by_query(&self, query: &str) -> Result<usize>;
     fn filter_issue(&self, issue: &Issue) -> bool;
+    fn repo_name(&self) -> (&str, &str);
 }
 
 #[async_trait]
@@ -56,13 +305,94 @@ impl IssueComponentTrait for RepoComponent {
         Ok(issues)
     }
 
+    async fn get_issue(&self, number: u64) -> Result<Issue> {
+        debug!("getting issue: {}", number);
+
+        self.rest
+            .get(&format!("{}/issues/{}", self.repo_path(), number))
+            .await
+    }
+
+    async fn create_issue(&self, options: &IssueCreateOptions) -> Result<Issue> {
+        info!("creating issue: {}", options.title);
+
+        self.rest
+            .post(&format!("{}/issues", self.repo_path()), options)
+            .await
+    }
+
+    /// Transfers the issue to the repo by the GraphQL API, as the REST API doesn't support it.
+    ///
+    /// Returns None if GitHub refuses to transfer the issue, e.g. to a repo of another owner.
+    /// Other failures like a timeout are errors, as the transfer may have been done.
+    async fn transfer_issue(&self, number: u64, owner: &str, repo: &str) -> Result<Option<u64>> {
+        info!("transferring issue {} to {}/{}", number, owner, repo);
+
+        let issue: serde_json::Value = self
+            .rest
+            .get(&format!("{}/issues/{}", self.repo_path(), number))
+            .await?;
+        let target_repo: serde_json::Value =
+            self.rest.get(&format!("repos/{}/{}", owner, repo)).await?;
+
+        let response: serde_json::Value = self
+            .rest
+            .post(
+                "graphql",
+                &serde_json::json!({
+                    "query": TRANSFER_ISSUE_MUTATION,
+                    "variables": {
+                        "issueId": issue["node_id"],
+                        "repositoryId": target_repo["node_id"],
+                    },
+                }),
+            )
+            .await?;
+
+        if let Some(errors) = response["errors"].as_array() {
+            if response["data"]["transferIssue"].is_null() {
+                warn!(
+                    "issue {} not transferable: {}",
+                    number,
+                    errors
+                        .iter()
+                        .filter_map(|it| it["message"].as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+                return Ok(None);
+            }
+        }
+
+        response["data"]["transferIssue"]["issue"]["number"]
+            .as_u64()
+            .map(Some)
+            .ok_or_else(|| anyhow!("failed to transfer issue {}: {}", number, response))
+    }
+
+    /// Lists the issues and pull requests of all states having all the labels, without the
+    /// configured filters and the search result limit.
+    async fn list_all_issues(&self, labels: &[&str]) -> Result<Vec<Issue>> {
+        debug!("listing all issues by labels: {:?}", labels);
+
+        let mut path = format!("{}/issues?state=all", self.repo_path());
+        if !labels.is_empty() {
+            path.push_str(&format!(
+                "&labels={}",
+                labels
+                    .iter()
+                    .map(|it| encode_path_segment(it))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ));
+        }
+
+        self.rest.get_all(&path).await
+    }
+
     async fn search_issues_by_labels(&self, labels: &[String]) -> Result<Vec<Issue>> {
         debug!("searching issues by labels: {:?}", labels);
 
-        let repo = self
-            .github
-            .repo(self.config.owner.clone(), self.config.repo.clone());
-
         let since_time = match self.config.since {
             Some(ref x) => {
                 x.clone()
@@ -72,47 +402,36 @@ impl IssueComponentTrait for RepoComponent {
             None => self.get_latest_release().await?.created_at,
         };
 
-        let mut search_options_builder = IssueListOptions::builder();
-        search_options_builder
-            .labels(labels.to_vec())
-            .state(to_issue_state(&self.config.state))
-            .sort(Sort::Created)
-            .since(since_time);
-        if let Some(IssueSort::Asc) = &self.config.sort {
-            search_options_builder.asc();
-        }
-        let search_options = search_options_builder.build();
-
-        let issues: Vec<_> = repo
-            .issues()
-            .iter(&search_options)
-            .filter_map(|it| {
-                if let Err(err) = it {
-                    error!("failed to parse the issue: {:?}", err);
-                    return None;
-                }
-                let issue = it.unwrap();
-
-                if self.filter_issue(&issue) {
-                    Some(issue)
-                } else {
-                    None
-                }
-            })
-            .collect()
-            .await;
-
-        Ok(issues)
+        let direction = match &self.config.sort {
+            Some(IssueSort::Asc) => "asc",
+            _ => "desc",
+        };
+        let issues: Vec<Issue> = self
+            .rest
+            .get_all(&format!(
+                "{}/issues?labels={}&state={}&sort=created&direction={}&since={}",
+                self.repo_path(),
+                labels
+                    .iter()
+                    .map(|it| encode_path_segment(it))
+                    .collect::<Vec<_>>()
+                    .join(","),
+                to_issue_state(&self.config.state),
+                direction,
+                encode_path_segment(&since_time)
+            ))
+            .await?;
+
+        Ok(issues
+            .into_iter()
+            .filter(|it| self.filter_issue(it))
+            .collect())
     }
 
     async fn search_issues_by_query(&self, query: &str) -> Result<Vec<Issue>> {
         use std::fmt::{Display, Write};
         debug!("Searching issues by query: {}", query);
 
-        let search_options = SearchIssuesOptions::builder()
-            .sort(IssuesSort::Created)
-            .build();
-
         fn add_filter(query: &mut String, filter: &str, args: impl Display) {
             write!(query, "{}:{} ", filter, args).unwrap();
         }
@@ -122,12 +441,6 @@ impl IssueComponentTrait for RepoComponent {
             query.push(' ');
         }
 
-        add_filter(
-            &mut query,
-            "repo",
-            format_args!("{}/{}", self.config.owner, self.config.repo),
-        );
-
         if let Some(milestone) = &self.config.milestone {
             add_filter(&mut query, "milestone", milestone);
         }
@@ -152,45 +465,149 @@ impl IssueComponentTrait for RepoComponent {
             query.pop();
         }
 
-        let issues: Vec<_> = self
-            .github
-            .search()
-            .issues()
-            .iter(query, &search_options)
-            .filter_map(|it| {
-                if let Err(err) = it {
-                    error!("failed to parse the issue: {}", err);
-                    return None;
-                }
+        let issues = self.search_all_issues_by_query(&query).await?;
+
+        Ok(issues
+            .into_iter()
+            .filter(|it| self.filter_issue(it))
+            .collect())
+    }
+
+    /// Searches issues by the query only, without the configured filters like the milestone.
+    ///
+    /// The search API provides up to 1000 results, so the rest are left out with a warning.
+    async fn search_all_issues_by_query(&self, query: &str) -> Result<Vec<Issue>> {
+        debug!("searching all issues by query: {}", query);
+
+        let query = format!("repo:{}/{} {}", self.config.owner, self.config.repo, query);
+        let mut issues = vec![];
+        for page in 1.. {
+            let result: SearchIssuesResult = self
+                .rest
+                .get(&format!(
+                    "search/issues?q={}&sort=created&per_page={}&page={}",
+                    encode_path_segment(query.trim_end()),
+                    SEARCH_PAGE_SIZE,
+                    page
+                ))
+                .await?;
+            let size = result.items.len();
+            issues.extend(result.items);
+
+            if size < SEARCH_PAGE_SIZE || page * SEARCH_PAGE_SIZE >= result.total_count {
+                break;
+            }
+
+            if page * SEARCH_PAGE_SIZE >= SEARCH_MAX_RESULTS {
+                warn!(
+                    "only the first {} of {} issues found by query are processed: {}",
+                    SEARCH_MAX_RESULTS,
+                    result.total_count,
+                    query.trim_end()
+                );
+                break;
+            }
+        }
 
-                match to_issue(&it.unwrap()) {
-                    Ok(issue) => {
-                        if self.filter_issue(&issue) {
-                            Some(issue)
-                        } else {
-                            None
-                        }
-                    }
-                    Err(err) => {
-                        error!("failed to convert the issue item to an issue: {}", err);
-                        None
-                    }
-                }
-            })
-            .collect()
-            .await;
         Ok(issues)
     }
 
-    async fn update_issues(&self, issues: &Vec<(u64, IssueOptions)>) -> Result<()> {
-        info!("updating issues: {:?}", issues);
+    async fn update_issues(
+        &self,
+        issues: &[IssueUpdate],
+        concurrency: usize,
+    ) -> Vec<IssueUpdateResult> {
+        info!(
+            "updating issues: concurrency: {}, {:?}",
+            concurrency, issues
+        );
 
-        let repo = self
-            .github
-            .repo(self.config.owner.clone(), self.config.repo.clone());
+        let semaphore = Semaphore::new(concurrency.max(1));
 
-        for (issue_number, issue) in issues {
-            repo.issues().update(issue_number, issue).await?;
+        join_all(issues.iter().map(|issue| async {
+            let _permit = semaphore.acquire().await;
+            self.update_issue_with_result(issue).await
+        }))
+        .await
+    }
+
+    async fn update_issue_with_result(&self, issue: &IssueUpdate) -> IssueUpdateResult {
+        if !issue.is_changed() {
+            return IssueUpdateResult::new(
+                issue,
+                IssueUpdateStatus::Skipped,
+                Some("no change".to_string()),
+            );
+        }
+
+        match self.update_issue(issue).await {
+            Ok(_) => IssueUpdateResult::new(issue, IssueUpdateStatus::Succeeded, None),
+            Err(err) => {
+                error!("failed to update the issue {}: {}", issue.number, err);
+                IssueUpdateResult::new(issue, IssueUpdateStatus::Failed, Some(err.to_string()))
+            }
+        }
+    }
+
+    async fn update_issue(&self, issue: &IssueUpdate) -> Result<()> {
+        debug!("updating issue: {:?}", issue);
+
+        let issue_path = format!("{}/issues/{}", self.repo_path(), issue.number);
+
+        if let Some(comment) = &issue.comment {
+            self.rest
+                .post::<_, serde_json::Value>(
+                    &format!("{}/comments", issue_path),
+                    &hashmap! { "body" => comment },
+                )
+                .await?;
+        }
+
+        let labels = issue.added_labels();
+        if !labels.is_empty() {
+            self.rest
+                .post::<_, serde_json::Value>(
+                    &format!("{}/labels", issue_path),
+                    &hashmap! { "labels" => labels },
+                )
+                .await?;
+        }
+
+        for label in issue.removed_labels() {
+            self.rest
+                .delete(&format!(
+                    "{}/labels/{}",
+                    issue_path,
+                    encode_path_segment(label)
+                ))
+                .await?;
+        }
+
+        let assignees = issue.added_assignees();
+        if !assignees.is_empty() {
+            self.rest
+                .post::<_, serde_json::Value>(
+                    &format!("{}/assignees", issue_path),
+                    &hashmap! { "assignees" => assignees },
+                )
+                .await?;
+        }
+
+        let assignees = issue.removed_assignees();
+        if !assignees.is_empty() {
+            self.rest
+                .delete_with_body::<_, serde_json::Value>(
+                    &format!("{}/assignees", issue_path),
+                    &hashmap! { "assignees" => assignees },
+                )
+                .await?;
+        }
+
+        let patch = issue.to_patch();
+        if !patch.is_empty() {
+            self.rest
+                .patch::<_, serde_json::Value>(&issue_path, &patch)
+                .await?;
         }
 
         Ok(())
@@ -204,9 +621,7 @@ impl IssueComponentTrait for RepoComponent {
             .repo(self.config.owner.clone(), self.config.repo.clone());
 
         let list_options = MilestoneListOptions::builder().build();
-        match repo
-            .milestones()
-            .list(&list_options)
+        match retry(|| repo.milestones().list(&list_options))
             .await?
             .into_iter()
             .find(|it| it.title == milestone)
@@ -216,6 +631,68 @@ impl IssueComponentTrait for RepoComponent {
         }
     }
 
+    async fn list_comments(&self, issue_number: u64) -> Result<Vec<Comment>> {
+        debug!("listing comments of issue: {}", issue_number);
+
+        self.rest
+            .get_all(&format!(
+                "{}/issues/{}/comments",
+                self.repo_path(),
+                issue_number
+            ))
+            .await
+    }
+
+    async fn create_comment(&self, issue_number: u64, body: &str) -> Result<Comment> {
+        info!("creating comment on issue: {}", issue_number);
+
+        let repo = self
+            .github
+            .repo(self.config.owner.clone(), self.config.repo.clone());
+
+        let comment = repo
+            .issues()
+            .get(issue_number)
+            .comments()
+            .create(&CommentOptions {
+                body: body.to_string(),
+            })
+            .await?;
+
+        Ok(comment)
+    }
+
+    async fn list_events_of_issue(&self, issue_number: u64) -> Result<Vec<IssueEvent>> {
+        debug!("listing events of issue: {}", issue_number);
+
+        self.rest
+            .get_all(&format!(
+                "{}/issues/{}/events",
+                self.repo_path(),
+                issue_number
+            ))
+            .await
+    }
+
+    async fn count_issues_by_query(&self, query: &str) -> Result<usize> {
+        debug!("counting issues by query: {}", query);
+
+        let query = format!("repo:{}/{} {}", self.config.owner, self.config.repo, query);
+        let result: SearchIssuesResult = self
+            .rest
+            .get(&format!(
+                "search/issues?q={}&per_page=1",
+                encode_path_segment(&query)
+            ))
+            .await?;
+
+        Ok(result.total_count)
+    }
+
+    fn repo_name(&self) -> (&str, &str) {
+        (&self.config.owner, &self.config.repo)
+    }
+
     fn filter_issue(&self, issue: &Issue) -> bool {
         trace!("filtering issue: {:?}", issue);
 
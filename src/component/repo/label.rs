@@ -0,0 +1,87 @@
+use async_trait::async_trait;
+use log::{debug, info};
+use serde::{Deserialize, Serialize};
+
+use crate::component::repo::RepoComponent;
+use crate::rest::encode_path_segment;
+use crate::result::Result;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Label {
+    pub name: String,
+    pub color: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct LabelUpdateOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+#[async_trait]
+pub trait LabelComponentTrait {
+    async fn list_labels(&self) -> Result<Vec<Label>>;
+    async fn find_label(&self, name: &str) -> Result<Option<Label>>;
+    async fn create_label(&self, label: &Label) -> Result<Label>;
+    async fn update_label(&self, name: &str, options: &LabelUpdateOptions) -> Result<Label>;
+    async fn delete_label(&self, name: &str) -> Result<()>;
+}
+
+#[async_trait]
+impl LabelComponentTrait for RepoComponent {
+    async fn list_labels(&self) -> Result<Vec<Label>> {
+        debug!("listing labels");
+
+        self.rest
+            .get_all(&format!("{}/labels", self.repo_path()))
+            .await
+    }
+
+    async fn find_label(&self, name: &str) -> Result<Option<Label>> {
+        debug!("finding label: {}", name);
+
+        // label names are case-insensitive
+        Ok(self
+            .list_labels()
+            .await?
+            .into_iter()
+            .find(|it| it.name.to_lowercase() == name.to_lowercase()))
+    }
+
+    async fn create_label(&self, label: &Label) -> Result<Label> {
+        info!("creating label: {:?}", label);
+
+        self.rest
+            .post(&format!("{}/labels", self.repo_path()), label)
+            .await
+    }
+
+    async fn update_label(&self, name: &str, options: &LabelUpdateOptions) -> Result<Label> {
+        info!("updating label {}: {:?}", name, options);
+
+        self.rest
+            .patch(
+                &format!("{}/labels/{}", self.repo_path(), encode_path_segment(name)),
+                options,
+            )
+            .await
+    }
+
+    async fn delete_label(&self, name: &str) -> Result<()> {
+        info!("deleting label: {}", name);
+
+        self.rest
+            .delete(&format!(
+                "{}/labels/{}",
+                self.repo_path(),
+                encode_path_segment(name)
+            ))
+            .await
+    }
+}
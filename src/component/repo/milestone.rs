@@ -0,0 +1,78 @@
+use anyhow::anyhow;
+use async_trait::async_trait;
+use hubcaps_ex::milestone::{Milestone, MilestoneOptions};
+use log::{debug, info};
+use serde::Serialize;
+
+use crate::component::repo::RepoComponent;
+use crate::result::Result;
+
+#[derive(Debug, Default, Serialize)]
+pub struct MilestoneUpdateOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_on: Option<String>,
+}
+
+#[async_trait]
+pub trait MilestoneComponentTrait {
+    async fn list_milestones(&self, state: &str) -> Result<Vec<Milestone>>;
+    async fn find_milestone(&self, milestone: &str) -> Result<Milestone>;
+    async fn create_milestone(&self, options: &MilestoneOptions) -> Result<Milestone>;
+    async fn update_milestone(
+        &self,
+        number: u64,
+        options: &MilestoneUpdateOptions,
+    ) -> Result<Milestone>;
+}
+
+#[async_trait]
+impl MilestoneComponentTrait for RepoComponent {
+    async fn list_milestones(&self, state: &str) -> Result<Vec<Milestone>> {
+        debug!("listing milestones: state: {}", state);
+
+        self.rest
+            .get_all(&format!("{}/milestones?state={}", self.repo_path(), state))
+            .await
+    }
+
+    async fn find_milestone(&self, milestone: &str) -> Result<Milestone> {
+        debug!("finding milestone: {}", milestone);
+
+        self.list_milestones("all")
+            .await?
+            .into_iter()
+            .find(|it| it.title == milestone)
+            .ok_or_else(|| anyhow!("milestone {} not found", milestone))
+    }
+
+    async fn create_milestone(&self, options: &MilestoneOptions) -> Result<Milestone> {
+        info!("creating milestone: {:?}", options);
+
+        let repo = self
+            .github
+            .repo(self.config.owner.clone(), self.config.repo.clone());
+
+        Ok(repo.milestones().create(options).await?)
+    }
+
+    async fn update_milestone(
+        &self,
+        number: u64,
+        options: &MilestoneUpdateOptions,
+    ) -> Result<Milestone> {
+        info!("updating milestone {}: {:?}", number, options);
+
+        self.rest
+            .patch(
+                &format!("{}/milestones/{}", self.repo_path(), number),
+                options,
+            )
+            .await
+    }
+}
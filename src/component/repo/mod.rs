@@ -3,15 +3,21 @@ use std::sync::Arc;
 use hubcaps_ex::Github;
 
 use crate::component::repo::issue::IssueComponentTrait;
+use crate::component::repo::label::LabelComponentTrait;
+use crate::component::repo::milestone::MilestoneComponentTrait;
 use crate::component::repo::release::ReleaseComponentTrait;
 use crate::config::NoteConfig;
+use crate::rest::RestClient;
 use crate::util::create_github_client;
 
 pub mod issue;
+pub mod label;
+pub mod milestone;
 pub mod release;
 
 pub struct RepoComponent {
     github: Arc<Github>,
+    rest: Arc<RestClient>,
     config: Arc<NoteConfig>,
 }
 
@@ -19,13 +25,23 @@ impl RepoComponent {
     pub fn new(
         github: Option<Arc<Github>>,
         config: Arc<NoteConfig>,
-    ) -> impl IssueComponentTrait + ReleaseComponentTrait {
+    ) -> impl IssueComponentTrait + LabelComponentTrait + MilestoneComponentTrait + ReleaseComponentTrait
+    {
         let github = if let Some(x) = github {
             x
         } else {
             Arc::new(create_github_client(&*config.token).unwrap())
         };
+        let rest = Arc::new(RestClient::new(&config.token).unwrap());
 
-        RepoComponent { github, config }
+        RepoComponent {
+            github,
+            rest,
+            config,
+        }
+    }
+
+    fn repo_path(&self) -> String {
+        format!("repos/{}/{}", self.config.owner, self.config.repo)
     }
 }
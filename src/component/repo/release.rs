@@ -4,10 +4,13 @@ use log::debug;
 
 use crate::component::repo::RepoComponent;
 use crate::result::Result;
+use crate::util::retry;
 
 #[async_trait]
 pub trait ReleaseComponentTrait {
     async fn get_latest_release(&self) -> Result<Release>;
+    async fn get_release_by_tag(&self, tag: &str) -> Result<Release>;
+    async fn get_previous_release(&self, release: &Release) -> Result<Option<Release>>;
 }
 
 #[async_trait]
@@ -16,6 +19,28 @@ impl ReleaseComponentTrait for RepoComponent {
         debug!("getting the latest release");
 
         let repo = self.github.repo(&self.config.owner, &self.config.repo);
-        Ok(repo.releases().latest().await?)
+        retry(|| repo.releases().latest()).await
+    }
+
+    async fn get_release_by_tag(&self, tag: &str) -> Result<Release> {
+        debug!("getting the release by tag: {}", tag);
+
+        let repo = self.github.repo(&self.config.owner, &self.config.repo);
+        retry(|| repo.releases().by_tag(tag)).await
+    }
+
+    /// Gets the latest published release created before the release.
+    async fn get_previous_release(&self, release: &Release) -> Result<Option<Release>> {
+        debug!("getting the release before: {}", release.tag_name);
+
+        let releases: Vec<Release> = self
+            .rest
+            .get_all(&format!("{}/releases", self.repo_path()))
+            .await?;
+
+        Ok(releases
+            .into_iter()
+            .filter(|it| !it.draft && it.created_at < release.created_at)
+            .max_by(|a, b| a.created_at.cmp(&b.created_at)))
     }
 }
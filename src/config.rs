@@ -19,6 +19,7 @@ pub struct NoteConfig {
     pub any_labels: Option<Vec<String>>,
     pub exclude_labels: Option<Vec<String>>,
     pub highlight_labels: Option<Vec<HighlightLabelConfig>>,
+    pub release_label: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
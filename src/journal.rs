@@ -0,0 +1,111 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use chrono::Utc;
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+use crate::component::repo::issue::IssueUpdate;
+use crate::result::Result;
+use crate::util::{get_command_line, get_data_dir};
+
+/// A record of a bulk issue update, to be able to undo it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub id: String,
+    pub timestamp: String,
+    pub command: String,
+    pub owner: String,
+    pub repo: String,
+    pub issues: Vec<IssueUpdate>,
+    /// The ID of the entry reverted by this entry, if it's an undo.
+    #[serde(default)]
+    pub undo_of: Option<String>,
+    #[serde(default)]
+    pub undone: bool,
+}
+
+impl JournalEntry {
+    pub fn new(owner: &str, repo: &str, issues: Vec<IssueUpdate>, undo_of: Option<&str>) -> Self {
+        let now = Utc::now();
+
+        JournalEntry {
+            id: now.format("%Y%m%d%H%M%S%3f").to_string(),
+            timestamp: now.to_rfc3339(),
+            command: get_command_line(),
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            issues,
+            undo_of: undo_of.map(|it| it.to_string()),
+            undone: false,
+        }
+    }
+
+    /// Creates the issue updates reverting the issues to the state before this entry.
+    pub fn to_undo_updates(&self) -> Vec<IssueUpdate> {
+        self.issues
+            .iter()
+            .map(|it| IssueUpdate {
+                number: it.number,
+                title: it.title.clone(),
+                before: it.after.clone(),
+                after: it.before.clone(),
+                state_reason: None,
+                comment: None,
+            })
+            .collect()
+    }
+}
+
+fn journal_dir() -> Result<PathBuf> {
+    get_data_dir("journal")
+}
+
+pub fn save_journal_entry(entry: &JournalEntry) -> Result<PathBuf> {
+    let dir = journal_dir()?;
+    fs::create_dir_all(&dir)?;
+
+    let path = dir.join(format!("{}.json", entry.id));
+    debug!("saving journal entry: {:?}", path);
+    fs::write(&path, serde_json::to_string_pretty(entry)?)?;
+
+    Ok(path)
+}
+
+pub fn load_journal_entry(id: &str) -> Result<JournalEntry> {
+    let path = journal_dir()?.join(format!("{}.json", id));
+    let content = fs::read_to_string(&path)
+        .map_err(|err| anyhow!("journal entry {} not found: {}", id, err))?;
+
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Gets the latest entry to undo, skipping the undone entries and the undo entries.
+pub fn get_latest_journal_entry() -> Result<JournalEntry> {
+    list_journal_entries()?
+        .into_iter()
+        .find(|it| it.undo_of.is_none() && !it.undone)
+        .ok_or_else(|| anyhow!("journal to undo not found"))
+}
+
+/// Lists the journal entries, the latest first.
+pub fn list_journal_entries() -> Result<Vec<JournalEntry>> {
+    let dir = journal_dir()?;
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut entries = vec![];
+    for file in fs::read_dir(dir)? {
+        let path = file?.path();
+        if path.extension().map_or(false, |it| it == "json") {
+            entries.push(serde_json::from_str::<JournalEntry>(&fs::read_to_string(
+                path,
+            )?)?);
+        }
+    }
+    entries.sort_by(|a, b| b.id.cmp(&a.id));
+
+    Ok(entries)
+}
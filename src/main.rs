@@ -1,5 +1,7 @@
 #![allow(where_clauses_object_safety)]
 
+#[macro_use]
+extern crate lazy_static;
 #[macro_use]
 extern crate libcli_rs;
 #[macro_use]
@@ -8,12 +10,20 @@ extern crate maplit;
 use clap::{Arg, Command, Error, ErrorKind};
 
 use crate::cmd::issue::*;
+use crate::cmd::label::*;
+use crate::cmd::milestone::*;
 use crate::cmd::note::*;
-use crate::cmd::{create_cmd, get_app_matches, CmdGroup};
+use crate::cmd::rules::*;
+use crate::cmd::serve::*;
+use crate::cmd::undo::*;
+use crate::cmd::{create_cmd, get_app_matches, resume_issue_updates, CmdGroup};
 
+mod checkpoint;
 mod cmd;
 mod component;
 mod config;
+mod journal;
+mod rest;
 mod result;
 mod util;
 
@@ -22,12 +32,17 @@ async fn main() {
     let commands: CmdGroup = hashmap! {
         CMD_ISSUE => create_cmd(Box::new(IssueCommand::new())),
         CMD_NOTE => create_cmd(Box::new(NoteCommand::new())),
+        CMD_LABEL => create_cmd(Box::new(LabelCommand::new())),
+        CMD_MILESTONE => create_cmd(Box::new(MilestoneCommand::new())),
+        CMD_RULES => create_cmd(Box::new(RulesCommand::new())),
+        CMD_SERVE => create_cmd(Box::new(ServeCommand::new())),
+        CMD_UNDO => create_cmd(Box::new(UndoCommand::new())),
     };
     let sub_commands: Vec<Command> = commands.values().map(|it| it.app()).collect();
 
     let app = Command::new(env!("CARGO_PKG_NAME"))
         .long_version(env!("LONG_VERSION"))
-        .about("A complementary Github tool to use with gh to extend note/issue/... experience")
+        .about("A complementary Github tool to use with gh to extend note/issue/label/milestone/... experience")
         .subcommands(sub_commands)
         .args([
             Arg::new("token")
@@ -38,6 +53,14 @@ async fn main() {
                 .short('t')
                 .env("GITHUB_TOKEN")
                 .takes_value(true),
+            Arg::new("api-url")
+                .value_name("url")
+                .help("GitHub API URL, e.g. a mock API for testing")
+                .global(true)
+                .long("api-url")
+                .env("GITHUB_API_URL")
+                .takes_value(true)
+                .default_value("https://api.github.com"),
             Arg::new("owner")
                 .value_name("string")
                 .help("GitHub owner")
@@ -72,9 +95,63 @@ async fn main() {
                 .takes_value(true)
                 .default_value("console")
                 .possible_values(["console", "json", "yaml"]),
+            Arg::new("dry-run")
+                .help("Show the changes without applying them, e.g. the issue changes of bulk updates")
+                .global(true)
+                .long("dry-run"),
+            Arg::new("yes")
+                .help("Update issues without confirmation")
+                .global(true)
+                .long("yes")
+                .short('y'),
+            Arg::new("max-issues")
+                .value_name("number")
+                .help("Maximum number of issues allowed to update at once")
+                .global(true)
+                .long("max-issues")
+                .takes_value(true)
+                .default_value("100"),
+            Arg::new("force")
+                .help("Update issues even if exceeding the maximum number of issues")
+                .global(true)
+                .long("force"),
+            Arg::new("max-retries")
+                .value_name("number")
+                .help("Maximum retries of rate limited or failed GitHub requests")
+                .global(true)
+                .long("max-retries")
+                .takes_value(true)
+                .default_value("3"),
+            Arg::new("timeout")
+                .value_name("seconds")
+                .help("Timeout of GitHub requests")
+                .global(true)
+                .long("timeout")
+                .takes_value(true)
+                .default_value("30"),
+            Arg::new("resume")
+                .value_name("checkpoint")
+                .help("Resume the remaining issue updates of the checkpoint ID or file")
+                .long("resume")
+                .takes_value(true),
+            Arg::new("concurrency")
+                .value_name("number")
+                .help("Number of issues updated concurrently")
+                .global(true)
+                .long("concurrency")
+                .takes_value(true)
+                .default_value("5"),
         ]);
 
-    if let Some((cmd, matches)) = get_app_matches(app).subcommand() {
+    let matches = get_app_matches(app);
+    if let Some(checkpoint) = matches.value_of("resume") {
+        if let Err(err) = resume_issue_updates(&matches, checkpoint).await {
+            Error::raw(ErrorKind::Format, err).exit();
+        }
+        return;
+    }
+
+    if let Some((cmd, matches)) = matches.subcommand() {
         let cmd = commands.get(cmd).unwrap();
 
         if let Err(err) = cmd.process(matches).await {
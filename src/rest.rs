@@ -0,0 +1,246 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::anyhow;
+use log::{debug, trace, warn};
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, RETRY_AFTER, USER_AGENT};
+use reqwest::{Client, Method, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::result::Result;
+use crate::util::{backoff_duration, client_setting};
+
+const PAGE_SIZE: usize = 100;
+
+/// A thin GitHub REST client for the endpoints not covered by hubcaps.
+///
+/// Rate limited requests are retried after the rate limit reset, and idempotent requests are
+/// retried with exponential backoff on server errors, both up to `--max-retries` times.
+pub struct RestClient {
+    client: Client,
+    api_url: String,
+}
+
+impl RestClient {
+    pub fn new(token: &str) -> Result<Self> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            ACCEPT,
+            HeaderValue::from_static("application/vnd.github.v3+json"),
+        );
+        headers.insert(USER_AGENT, HeaderValue::from_static(env!("CARGO_PKG_NAME")));
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("token {}", token))?,
+        );
+
+        let setting = client_setting();
+        let client = Client::builder()
+            .default_headers(headers)
+            .timeout(setting.timeout)
+            .build()?;
+
+        Ok(RestClient {
+            client,
+            api_url: setting.api_url,
+        })
+    }
+
+    pub async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        self.send::<(), _>(Method::GET, path, None).await
+    }
+
+    pub async fn get_all<T: DeserializeOwned>(&self, path: &str) -> Result<Vec<T>> {
+        let separator = if path.contains('?') { '&' } else { '?' };
+        let mut items = vec![];
+
+        for page in 1.. {
+            let mut page_items: Vec<T> = self
+                .get(&format!(
+                    "{}{}per_page={}&page={}",
+                    path, separator, PAGE_SIZE, page
+                ))
+                .await?;
+            let size = page_items.len();
+            items.append(&mut page_items);
+
+            if size < PAGE_SIZE {
+                break;
+            }
+        }
+
+        Ok(items)
+    }
+
+    pub async fn post<B: Serialize + ?Sized, T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T> {
+        self.send(Method::POST, path, Some(body)).await
+    }
+
+    pub async fn patch<B: Serialize + ?Sized, T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T> {
+        self.send(Method::PATCH, path, Some(body)).await
+    }
+
+    pub async fn delete(&self, path: &str) -> Result<()> {
+        self.send::<(), serde_json::Value>(Method::DELETE, path, None)
+            .await
+            .map(|_| ())
+    }
+
+    pub async fn delete_with_body<B: Serialize + ?Sized, T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T> {
+        self.send(Method::DELETE, path, Some(body)).await
+    }
+
+    async fn send<B: Serialize + ?Sized, T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&B>,
+    ) -> Result<T> {
+        let max_retries = client_setting().max_retries;
+        let idempotent = matches!(
+            method,
+            Method::GET | Method::HEAD | Method::PUT | Method::DELETE
+        );
+
+        for attempt in 0.. {
+            debug!("requesting {} {}", method, path);
+
+            let mut request = self
+                .client
+                .request(method.clone(), format!("{}/{}", self.api_url, path));
+            if let Some(body) = body {
+                request = request.json(body);
+            }
+
+            let response = match request.send().await {
+                Ok(response) => response,
+                Err(err) if idempotent && attempt < max_retries && is_transient_error(&err) => {
+                    let wait = backoff_duration(attempt);
+                    warn!(
+                        "{} {} failed: {}, retrying in {:?}",
+                        method, path, err, wait
+                    );
+                    tokio::time::sleep(wait).await;
+                    continue;
+                }
+                Err(err) => return Err(err.into()),
+            };
+
+            log_rate_limit(&response);
+
+            let status = response.status();
+            if attempt < max_retries {
+                if let Some(wait) = rate_limit_wait(&response) {
+                    warn!("{} {} rate limited, retrying in {:?}", method, path, wait);
+                    tokio::time::sleep(wait).await;
+                    continue;
+                }
+
+                if idempotent && status.is_server_error() {
+                    let wait = backoff_duration(attempt);
+                    warn!(
+                        "{} {} failed: {}, retrying in {:?}",
+                        method, path, status, wait
+                    );
+                    tokio::time::sleep(wait).await;
+                    continue;
+                }
+            }
+
+            let text = response.text().await?;
+            trace!("received response: {} {}", status, text);
+
+            if !status.is_success() {
+                return Err(anyhow!("GitHub API error ({}): {}", status, text));
+            }
+
+            if text.is_empty() {
+                return Ok(serde_json::from_str("null")?);
+            }
+
+            return Ok(serde_json::from_str(&text)?);
+        }
+
+        unreachable!()
+    }
+}
+
+/// Encodes a value like a label name to be used as a path segment.
+pub fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+
+    encoded
+}
+
+fn is_transient_error(err: &reqwest::Error) -> bool {
+    err.is_timeout() || err.is_connect()
+}
+
+fn header_value<'a>(response: &'a Response, name: &str) -> Option<&'a str> {
+    response.headers().get(name).and_then(|it| it.to_str().ok())
+}
+
+fn log_rate_limit(response: &Response) {
+    if let (Some(remaining), Some(limit)) = (
+        header_value(response, "x-ratelimit-remaining"),
+        header_value(response, "x-ratelimit-limit"),
+    ) {
+        debug!(
+            "rate limit: {}/{} remaining, reset at {}",
+            remaining,
+            limit,
+            header_value(response, "x-ratelimit-reset").unwrap_or_default()
+        );
+    }
+}
+
+/// Returns how long to wait if the request is rate limited, by the primary or secondary rate limit.
+fn rate_limit_wait(response: &Response) -> Option<Duration> {
+    if !matches!(
+        response.status(),
+        StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS
+    ) {
+        return None;
+    }
+
+    if let Some(seconds) = response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|it| it.to_str().ok())
+        .and_then(|it| it.parse::<u64>().ok())
+    {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    if header_value(response, "x-ratelimit-remaining") == Some("0") {
+        let reset = header_value(response, "x-ratelimit-reset")?
+            .parse::<u64>()
+            .ok()?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+
+        return Some(Duration::from_secs(reset.saturating_sub(now) + 1));
+    }
+
+    None
+}
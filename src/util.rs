@@ -1,22 +1,152 @@
+use std::future::Future;
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::RwLock;
+use std::time::Duration;
 
 use anyhow::anyhow;
 use clap::ArgMatches;
 use hubcaps_ex::{Credentials, Github};
 use libcli_rs::output::OutputFormat;
-use log::Level;
+use log::{warn, Level};
 
 use crate::result::Result;
 
+const GITHUB_API_HOST: &str = "https://api.github.com";
+const MAX_BACKOFF_SECS: u64 = 60;
+const SECRET_ARGS: [&str; 3] = ["--token", "-t", "--secret"];
+const REDACTED: &str = "***";
+
+#[derive(Debug, Clone)]
+pub struct ClientSetting {
+    pub api_url: String,
+    pub max_retries: u32,
+    pub timeout: Duration,
+}
+
+impl Default for ClientSetting {
+    fn default() -> Self {
+        ClientSetting {
+            api_url: GITHUB_API_HOST.to_string(),
+            max_retries: 3,
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+lazy_static! {
+    static ref CLIENT_SETTING: RwLock<ClientSetting> = RwLock::new(ClientSetting::default());
+}
+
 pub fn create_github_client(token: &str) -> Result<Github> {
-    let client = Github::new(
+    let setting = client_setting();
+    let http_client = reqwest::Client::builder()
+        .timeout(setting.timeout)
+        .build()?;
+
+    let client = Github::custom(
+        setting.api_url,
         env!("CARGO_PKG_NAME"),
         Credentials::Token(token.to_string()),
-    )?;
+        http_client,
+    );
 
     Ok(client)
 }
 
+pub fn init_client_setting(args: &ArgMatches) -> Result<()> {
+    let mut setting = CLIENT_SETTING.write().unwrap();
+
+    if let Some(api_url) = args.value_of("api-url") {
+        setting.api_url = api_url.trim_end_matches('/').to_string();
+    }
+    if args.is_present("max-retries") {
+        setting.max_retries = args.value_of_t("max-retries")?;
+    }
+    if args.is_present("timeout") {
+        setting.timeout = Duration::from_secs(args.value_of_t("timeout")?);
+    }
+
+    Ok(())
+}
+
+pub fn client_setting() -> ClientSetting {
+    CLIENT_SETTING.read().unwrap().clone()
+}
+
+pub fn backoff_duration(attempt: u32) -> Duration {
+    Duration::from_secs(2u64.saturating_pow(attempt).min(MAX_BACKOFF_SECS))
+}
+
+/// Retries the GitHub request by hubcaps if rate limited or failed by a server error.
+///
+/// Only use it for idempotent requests.
+pub async fn retry<T, F, Fut>(request: F) -> Result<T>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = hubcaps_ex::Result<T>>,
+{
+    let max_retries = client_setting().max_retries;
+
+    for attempt in 0.. {
+        let wait = match request().await {
+            Err(hubcaps_ex::Error::RateLimit { reset }) if attempt < max_retries => reset,
+            Err(hubcaps_ex::Error::Fault { code, .. })
+                if code.is_server_error() && attempt < max_retries =>
+            {
+                backoff_duration(attempt)
+            }
+            result => return Ok(result?),
+        };
+
+        warn!("GitHub request failed, retrying in {:?}", wait);
+        tokio::time::sleep(wait).await;
+    }
+
+    unreachable!()
+}
+
+/// Gets the directory under the local data directory to keep the local states like the journal.
+pub fn get_data_dir(name: &str) -> Result<PathBuf> {
+    let dir = dirs::data_local_dir()
+        .ok_or_else(|| anyhow!("local data directory not found"))?
+        .join(env!("CARGO_PKG_NAME"))
+        .join(name);
+
+    Ok(dir)
+}
+
+/// Gets the command line of the current process with the values of the secret args redacted.
+pub fn get_command_line() -> String {
+    let mut args = vec![];
+    let mut redact_next = false;
+
+    for arg in std::env::args() {
+        if redact_next {
+            args.push(REDACTED.to_string());
+            redact_next = false;
+            continue;
+        }
+
+        match SECRET_ARGS.iter().find(|it| arg.starts_with(*it)) {
+            Some(name) if arg == *name => {
+                redact_next = true;
+                args.push(arg);
+            }
+            Some(name) if name.starts_with("--") && arg.starts_with(&format!("{}=", name)) => {
+                args.push(format!("{}={}", name, REDACTED));
+            }
+            // short args like -tTOKEN
+            Some(name) if !name.starts_with("--") => {
+                args.push(format!("{}{}", name, REDACTED));
+            }
+            _ => args.push(arg),
+        }
+    }
+
+    args.join(" ")
+}
+
 pub fn get_output_format_from_args(args: &ArgMatches) -> Result<OutputFormat> {
     let format = args.value_of("format").unwrap();
     OutputFormat::from_str(format).map_err(|err| anyhow!("{:?}", err))